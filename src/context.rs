@@ -1,16 +1,48 @@
+use crate::{diagnostic::Diagnostic, log::color, tsconfig_path::TsConfigInfo};
+use std::sync::Arc;
+
+/// The state shared by a resolving, it is passed to `ResolverPlugin`
+/// so that the plugin can resolve another request in the same resolving.
 #[derive(Debug)]
 pub struct Context {
-    pub depth: Depth,
-    pub fully_specified: Bool,
-    pub resolve_to_context: Bool,
+    pub(crate) depth: Depth,
+    pub(crate) fully_specified: Bool,
+    pub(crate) resolve_to_context: Bool,
+    /// Whether the `modules` are looked up in the ancestor directory for an
+    /// outer lookup, only the outermost one looks up the global folders.
+    pub(crate) lookup_ancestor: Bool,
+    /// The parsed `tsconfig` in options, it is `None`
+    /// before the tsconfig is loaded.
+    pub(crate) tsconfig: Option<Arc<TsConfigInfo>>,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+    /// The address of the alias keys which are rewriting the request,
+    /// from the outermost to the innermost.
+    pub(crate) alias_keys: Vec<usize>,
 }
 
 impl Context {
-    pub fn new(fully_specified: bool, resolve_to_context: bool) -> Self {
+    pub(crate) fn new(fully_specified: bool, resolve_to_context: bool) -> Self {
         Self {
             depth: Depth::new(),
             fully_specified: Bool(fully_specified),
             resolve_to_context: Bool(resolve_to_context),
+            lookup_ancestor: Bool(false),
+            tsconfig: None,
+            diagnostics: vec![],
+            alias_keys: vec![],
+        }
+    }
+
+    /// Report a diagnostic, the duplicated one is ignored.
+    pub fn report(&mut self, diagnostic: Diagnostic) {
+        tracing::debug!(
+            "{:?} in '{}': {}",
+            diagnostic.severity,
+            color::blue(&diagnostic.location.display()),
+            diagnostic.message
+        );
+        if !self.diagnostics.contains(&diagnostic) {
+            self.diagnostics.push(diagnostic);
         }
     }
 }
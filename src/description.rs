@@ -1,55 +1,129 @@
 use crate::info::NormalizedPath;
-use crate::{AliasMap, Error, RResult};
-use once_cell::sync::OnceCell;
-use std::path::Path;
+use crate::{AliasMap, Error, ParseError, RResult};
+use dashmap::DashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+pub type AliasField = Vec<(String, AliasMap)>;
+
+/// Parses the content of the description file located at the path to a JSON value.
+pub type DescriptionParser = fn(&str, &Path) -> RResult<serde_json::Value>;
+
+/// Identifies the parsers by the extension and the address of function,
+/// the parsed description data is cached for each of them.
+pub(crate) type ParsersKey = Box<[(String, usize)]>;
+
+pub(crate) fn parsers_key(parsers: &[(String, DescriptionParser)]) -> ParsersKey {
+    parsers
+        .iter()
+        .map(|(ext, parser)| (ext.clone(), *parser as usize))
+        .collect()
+}
+
+/// The built-in parsers, keyed by the extension of description file.
+pub(crate) fn default_description_parsers() -> Vec<(String, DescriptionParser)> {
+    let parsers: [(&str, DescriptionParser); 4] = [
+        ("json", parse_json),
+        ("json5", parse_json5),
+        ("yaml", parse_yaml),
+        ("yml", parse_yaml),
+    ];
+    parsers
+        .into_iter()
+        .map(|(ext, parser)| (ext.to_string(), parser))
+        .collect()
+}
+
+fn parse_json(content: &str, file_path: &Path) -> RResult<serde_json::Value> {
+    tracing::debug_span!("serde_json_from_str").in_scope(|| {
+        serde_json::from_str(content)
+            .map_err(|error| Error::UnexpectedJson((file_path.into(), error.into())))
+    })
+}
+
+/// Accepts comments, trailing commas and unquoted keys.
+fn parse_json5(content: &str, file_path: &Path) -> RResult<serde_json::Value> {
+    jsonc_parser::parse_to_serde_value(content, &Default::default())
+        .map_err(|error| {
+            let error = ParseError::at_offset(error.message, content, error.range.start);
+            Error::UnexpectedJson((file_path.into(), error))
+        })
+        .map(|value| value.unwrap_or_default())
+}
+
+fn parse_yaml(content: &str, file_path: &Path) -> RResult<serde_json::Value> {
+    serde_yaml::from_str(content).map_err(|error| {
+        let (line, column) = error
+            .location()
+            .map_or((0, 0), |location| (location.line(), location.column()));
+        // the message of serde_yaml contains the location.
+        let message = error
+            .to_string()
+            .replace(&format!(" at line {line} column {column}"), "");
+        Error::UnexpectedJson((file_path.into(), ParseError::new(message, line, column)))
+    })
+}
+
 #[derive(Debug)]
 pub struct PkgJSON {
     name: Option<Box<str>>,
-    alias_fields: OnceCell<Vec<(String, AliasMap)>>,
+    alias_fields: DashMap<Vec<String>, Arc<AliasField>>,
     raw: Arc<serde_json::Value>,
 }
 
 impl PkgJSON {
-    pub(crate) fn parse(content: &str, file_path: &Path) -> RResult<Self> {
-        let json: serde_json::Value =
-            tracing::debug_span!("serde_json_from_str").in_scope(|| {
-                serde_json::from_str(content)
-                    .map_err(|error| Error::UnexpectedJson((file_path.into(), error)))
-            })?;
+    pub(crate) fn parse(
+        content: &str,
+        file_path: &Path,
+        parsers: &[(String, DescriptionParser)],
+    ) -> RResult<Self> {
+        let ext = file_path
+            .extension()
+            .map(|ext| ext.to_string_lossy())
+            .unwrap_or_default();
+        let Some((_, parser)) = parsers.iter().find(|(key, _)| *key == ext) else {
+            return Err(Error::UnexpectedValue(format!(
+                "No parser for description file {}",
+                file_path.display()
+            )));
+        };
+        let json = parser(content, file_path)?;
 
         let name = json.get("name").and_then(|v| v.as_str()).map(|s| s.into());
 
         Ok(Self {
             name,
-            alias_fields: OnceCell::new(),
+            alias_fields: Default::default(),
             raw: Arc::from(json),
         })
     }
 
-    pub fn alias_fields(&self) -> &Vec<(String, AliasMap)> {
-        self.alias_fields.get_or_init(|| {
-            let mut alias_fields = Vec::new();
-
-            if let Some(value) = self.raw.get("browser") {
-                // https://github.com/defunctzombie/package-browser-field-spec
-                if let Some(map) = value.as_object() {
-                    for (key, value) in map {
-                        if let Some(false) = value.as_bool() {
-                            alias_fields.push((key.to_string(), AliasMap::Ignored));
-                        } else if let Some(s) = value.as_str() {
-                            alias_fields.push((key.to_string(), AliasMap::Target(s.to_string())));
-                        }
+    /// Returns the parsed alias map of `field`, which is cached for each field.
+    pub fn alias_field(&self, field: &Vec<String>) -> Arc<AliasField> {
+        if let Some(alias_field) = self.alias_fields.get(field) {
+            return alias_field.clone();
+        }
+        let mut alias_field = Vec::new();
+        if let Some(value) = self.get_filed(field) {
+            // https://github.com/defunctzombie/package-browser-field-spec
+            if let Some(map) = value.as_object() {
+                for (key, value) in map {
+                    if let Some(false) = value.as_bool() {
+                        alias_field.push((key.to_string(), AliasMap::Ignored));
+                    } else if let Some(s) = value.as_str() {
+                        alias_field.push((key.to_string(), AliasMap::Target(s.to_string())));
                     }
-                } else if let Some(false) = value.as_bool() {
-                    alias_fields.push((String::from("."), AliasMap::Ignored));
-                } else if let Some(s) = value.as_str() {
-                    alias_fields.push((String::from("."), AliasMap::Target(s.to_string())));
                 }
+            } else if let Some(false) = value.as_bool() {
+                alias_field.push((String::from("."), AliasMap::Ignored));
+            } else if let Some(s) = value.as_str() {
+                alias_field.push((String::from("."), AliasMap::Target(s.to_string())));
             }
-            alias_fields
-        })
+        }
+        self.alias_fields
+            .entry(field.clone())
+            .or_insert_with(|| Arc::new(alias_field))
+            .clone()
     }
 
     pub(crate) fn get_filed(&self, field: &Vec<String>) -> Option<&serde_json::Value> {
@@ -81,13 +155,17 @@ pub struct DescriptionData {
     /// The path to the directory where the description file located.
     /// It not a property in package.json.
     dir_path: NormalizedPath,
+    /// The path of the description file.
+    path: PathBuf,
 }
 
 impl DescriptionData {
-    pub fn new<P: AsRef<Path>>(json: PkgJSON, dir_path: P) -> Self {
+    pub fn new<P: AsRef<Path>>(json: PkgJSON, path: P) -> Self {
+        let path = path.as_ref();
         Self {
             json,
-            dir_path: NormalizedPath::new(dir_path),
+            dir_path: NormalizedPath::new(path.parent().unwrap()),
+            path: path.to_path_buf(),
         }
     }
 
@@ -95,6 +173,10 @@ impl DescriptionData {
         &self.dir_path
     }
 
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     pub fn data(&self) -> &PkgJSON {
         &self.json
     }
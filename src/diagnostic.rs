@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Hint,
+}
+
+/// The non-fatal problem found during the resolving, it never
+/// changes the result of resolving.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The file where the problem comes from, such as the
+    /// description file or tsconfig.
+    pub location: PathBuf,
+    /// The keys from the root of `location` to the value which causes
+    /// the problem, such as `["exports", "./lib/"]`, it is empty if the
+    /// problem is not caused by a value.
+    pub key_path: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn warning(message: String, location: PathBuf) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message,
+            location,
+            key_path: vec![],
+        }
+    }
+
+    pub fn with_key_path(mut self, key_path: Vec<String>) -> Self {
+        self.key_path = key_path;
+        self
+    }
+}
@@ -1,13 +1,17 @@
 use once_cell::sync::OnceCell;
 use std::{
     borrow::Cow,
+    ffi::OsString,
     fs::FileType,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::SystemTime,
 };
 
-use crate::{description::DescriptionData, Error, RResult, Resolver};
+use crate::{
+    description::{parsers_key, DescriptionData, ParsersKey},
+    Context, Diagnostic, Error, Options, RResult, Resolver,
+};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct EntryStat {
@@ -48,18 +52,58 @@ impl EntryStat {
     }
 }
 
+/// The options which the description data depends on.
+#[derive(Debug)]
+struct PkgInfoKey {
+    description_files: Box<[String]>,
+    parsers: ParsersKey,
+    ignore_invalid_description_files: bool,
+}
+
+impl PkgInfoKey {
+    fn new(options: &Options) -> Self {
+        Self {
+            description_files: options.description_files.clone().into_boxed_slice(),
+            parsers: parsers_key(&options.description_parsers),
+            ignore_invalid_description_files: options.ignore_invalid_description_files,
+        }
+    }
+
+    fn matches(&self, options: &Options) -> bool {
+        *self.description_files == *options.description_files
+            && self.ignore_invalid_description_files == options.ignore_invalid_description_files
+            && self.parsers.len() == options.description_parsers.len()
+            && self.parsers.iter().zip(&options.description_parsers).all(
+                |((ext, address), (other_ext, parser))| {
+                    ext == other_ext && *address == *parser as usize
+                },
+            )
+    }
+}
+
+/// The description data and the warnings of ignored description files.
+type PkgInfo = (Option<Arc<DescriptionData>>, Arc<[Diagnostic]>);
+
+type PkgInfoMemo = (PkgInfoKey, PkgInfo);
+
 #[derive(Debug)]
 pub struct Entry {
     parent: Option<Arc<Entry>>,
     path: Box<Path>,
-    // None: package.json does not exist
-    pkg_info: OnceCell<Option<Arc<DescriptionData>>>,
+    /// The description options and the description data found by them,
+    /// None: description file does not exist
+    pkg_info: OnceCell<PkgInfoMemo>,
+    /// The description data found by the other description options.
+    other_pkg_infos: Mutex<Vec<PkgInfoMemo>>,
     stat: OnceCell<EntryStat>,
     /// None represent the `self.path` is not a symlink
     symlink: OnceCell<Option<Box<Path>>>,
     /// If `self.path` is a symlink, then return canonicalized path,
     /// else return `self.path`
     real: OnceCell<Box<Path>>,
+    /// The sorted names in `self.path`,
+    /// None represent the `self.path` is not a directory
+    children: OnceCell<Option<Arc<[OsString]>>>,
 }
 
 impl Entry {
@@ -71,40 +115,104 @@ impl Entry {
         self.parent.as_ref()
     }
 
-    pub fn pkg_info(&self, resolver: &Resolver) -> RResult<&Option<Arc<DescriptionData>>> {
-        self.pkg_info.get_or_try_init(|| {
-            let pkg_name = &resolver.options.description_file;
-            let path = self.path();
-            let is_pkg_suffix = path.ends_with(pkg_name);
-            if self.is_dir() || is_pkg_suffix {
-                let pkg_path = if is_pkg_suffix {
-                    Cow::Borrowed(path)
-                } else {
-                    Cow::Owned(path.join(pkg_name))
-                };
-                match resolver
-                    .cache
-                    .fs
-                    .read_description_file(&pkg_path, EntryStat::default())
-                {
-                    Ok(info) => {
-                        return Ok(Some(info));
-                    }
-                    Err(error @ (Error::UnexpectedJson(_) | Error::UnexpectedValue(_))) => {
-                        // Return bad json
-                        return Err(error);
-                    }
-                    Err(Error::Io(_)) => {
-                        // package.json not found
-                    }
-                    _ => unreachable!(),
-                };
-            }
-            if let Some(parent) = &self.parent() {
-                return parent.pkg_info(resolver).cloned();
+    /// Returns the nearest description data and reports the warnings of
+    /// the ignored invalid description files to `context`.
+    pub fn pkg_info(
+        &self,
+        resolver: &Resolver,
+        context: &mut Context,
+    ) -> RResult<Option<Arc<DescriptionData>>> {
+        let (pkg_info, warnings) = self.memoized_pkg_info(resolver)?;
+        for warning in warnings.iter() {
+            context.report(warning.clone());
+        }
+        Ok(pkg_info)
+    }
+
+    /// The description data is memoized with the warnings for each
+    /// `description_files`, `description_parsers` and
+    /// `ignore_invalid_description_files` since the cache may be shared
+    /// by resolvers.
+    fn memoized_pkg_info(&self, resolver: &Resolver) -> RResult<PkgInfo> {
+        let options = &resolver.options;
+        let (memo_key, memo) = self.pkg_info.get_or_try_init(|| {
+            let pkg_info = self.load_pkg_info(resolver)?;
+            RResult::Ok((PkgInfoKey::new(options), pkg_info))
+        })?;
+        if memo_key.matches(options) {
+            return Ok(memo.clone());
+        }
+
+        let mut other_pkg_infos = self.other_pkg_infos.lock().unwrap();
+        if let Some((_, pkg_info)) = other_pkg_infos.iter().find(|(key, _)| key.matches(options)) {
+            return Ok(pkg_info.clone());
+        }
+        let pkg_info = self.load_pkg_info(resolver)?;
+        other_pkg_infos.push((PkgInfoKey::new(options), pkg_info.clone()));
+        Ok(pkg_info)
+    }
+
+    fn load_pkg_info(&self, resolver: &Resolver) -> RResult<PkgInfo> {
+        let path = self.path();
+        let description_files = &resolver.options.description_files;
+        let pkg_paths = if description_files.iter().any(|name| path.ends_with(name)) {
+            vec![Cow::Borrowed(path)]
+        } else if self.is_dir() {
+            description_files
+                .iter()
+                .map(|name| Cow::Owned(path.join(name)))
+                .collect()
+        } else {
+            vec![]
+        };
+        let mut warnings = vec![];
+        for pkg_path in pkg_paths {
+            if !resolver.load_entry(&pkg_path).is_file() {
+                continue;
             }
-            Ok(None)
-        })
+            match resolver.cache.fs.read_description_file(
+                &pkg_path,
+                EntryStat::default(),
+                &resolver.options.description_parsers,
+            ) {
+                Ok(info) => {
+                    return Ok((Some(info), warnings.into()));
+                }
+                Err(Error::UnexpectedJson((path, error)))
+                    if resolver.options.ignore_invalid_description_files =>
+                {
+                    warnings.push(Diagnostic::warning(
+                        format!(
+                            "Ignored invalid description file '{}', {error}",
+                            path.display()
+                        ),
+                        path.to_path_buf(),
+                    ));
+                }
+                Err(Error::UnexpectedValue(msg))
+                    if resolver.options.ignore_invalid_description_files =>
+                {
+                    warnings.push(Diagnostic::warning(
+                        format!("Ignored invalid description file, {msg}"),
+                        pkg_path.to_path_buf(),
+                    ));
+                }
+                Err(error @ (Error::UnexpectedJson(_) | Error::UnexpectedValue(_))) => {
+                    // Return bad json
+                    return Err(error);
+                }
+                Err(Error::Io(_)) => {
+                    // description file not found
+                }
+                _ => unreachable!(),
+            };
+        }
+        if let Some(parent) = &self.parent() {
+            let (pkg_info, parent_warnings) = parent.memoized_pkg_info(resolver)?;
+            warnings.extend(parent_warnings.iter().cloned());
+            return Ok((pkg_info, warnings.into()));
+        }
+        Ok((None, warnings.into()))
     }
 
     pub fn is_file(&self) -> bool {
@@ -127,6 +235,18 @@ impl Entry {
         *self.stat.get_or_init(|| EntryStat::stat(&self.path))
     }
 
+    /// Stats the entry if it has not been stat'ed, returns the stat only
+    /// to the caller which did it.
+    fn init_stat(&self) -> Option<EntryStat> {
+        let mut fresh = None;
+        self.stat.get_or_init(|| {
+            let stat = EntryStat::stat(&self.path);
+            fresh = Some(stat);
+            stat
+        });
+        fresh
+    }
+
     pub fn real(&self) -> Option<&Path> {
         self.real.get().map(|p| &**p)
     }
@@ -135,6 +255,22 @@ impl Entry {
         self.real.get_or_init(|| path);
     }
 
+    /// Returns the sorted names listed in `self.path`.
+    /// Returns None if `self.path` is not a directory.
+    pub fn children(&self) -> Option<Arc<[OsString]>> {
+        self.children
+            .get_or_init(|| {
+                let read_dir = std::fs::read_dir(&self.path).ok()?;
+                let mut names = read_dir
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.file_name())
+                    .collect::<Vec<_>>();
+                names.sort();
+                Some(names.into())
+            })
+            .clone()
+    }
+
     /// Returns the canonicalized path of `self.path` if it is a symlink.
     /// Returns None if `self.path` is not a symlink.
     pub fn symlink(&self) -> &Option<Box<Path>> {
@@ -152,7 +288,19 @@ impl Entry {
 }
 
 impl Resolver {
+    /// Returns the entry of `path`, and notify `on_file_probe` with
+    /// whether it exists when the entry is stat'ed for the first time.
     pub(super) fn load_entry(&self, path: &Path) -> Arc<Entry> {
+        let entry = self.load_cached_entry(path);
+        if let Some(on_file_probe) = &self.options.lifecycle_hooks.on_file_probe {
+            if let Some(stat) = entry.init_stat() {
+                on_file_probe(path, stat.file_type().is_some());
+            }
+        }
+        entry
+    }
+
+    fn load_cached_entry(&self, path: &Path) -> Arc<Entry> {
         if let Some(cached) = self.cache.entries.get(path) {
             cached.clone()
         } else {
@@ -167,7 +315,7 @@ impl Resolver {
 
     fn load_entry_uncached(&self, path: &Path) -> Entry {
         let parent = if let Some(parent) = path.parent() {
-            let entry = self.load_entry(parent);
+            let entry = self.load_cached_entry(parent);
             Some(entry)
         } else {
             None
@@ -176,9 +324,11 @@ impl Resolver {
             parent,
             path: path.into(),
             pkg_info: OnceCell::default(),
+            other_pkg_infos: Mutex::default(),
             stat: OnceCell::default(),
             symlink: OnceCell::default(),
             real: OnceCell::default(),
+            children: OnceCell::default(),
         }
     }
 
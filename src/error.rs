@@ -3,11 +3,25 @@ use std::{io, path::Path};
 #[derive(Debug)]
 pub enum Error {
     Io(io::Error),
-    UnexpectedJson((Box<Path>, serde_json::Error)),
+    /// The file can't be parsed, such as the malformed description file
+    /// in any of the `description_parsers`.
+    UnexpectedJson((Box<Path>, ParseError)),
     UnexpectedValue(String),
     ResolveFailedTag,
     Overflow,
     CantFindTsConfig(Box<Path>),
+    /// The resolved path mismatches the case of the actual path, returned by
+    /// `case_mismatch` with `CheckLevel::Fail`.
+    CaseMismatch((Box<Path>, String)),
+    /// The resolved package is not declared in the dependencies of the
+    /// description file, returned by `phantom_dependencies` with `CheckLevel::Fail`.
+    PhantomDependency((Box<Path>, String)),
+    /// The installed version does not satisfy the declared range, returned
+    /// by `version_mismatch` with `CheckLevel::Fail`.
+    VersionMismatch((Box<Path>, String)),
+    /// The resolving failed by the inner error, with the "did you mean"
+    /// candidates found by `suggestions`, the nearest one is the first.
+    Suggested((Box<Error>, Vec<String>)),
 }
 
 impl From<std::io::Error> for Error {
@@ -15,3 +29,69 @@ impl From<std::io::Error> for Error {
         Self::Io(value)
     }
 }
+
+/// Where and why a file can't be parsed, the line and column start
+/// from 1, or are 0 if the parser does not know the location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ParseError {
+    pub fn new(message: String, line: usize, column: usize) -> Self {
+        Self {
+            message,
+            line,
+            column,
+        }
+    }
+
+    /// Creates the error located at the byte `offset` of `content`.
+    pub fn at_offset(message: String, content: &str, offset: usize) -> Self {
+        let before = &content[..offset.min(content.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map_or(before, |index| &before[index + 1..])
+            .chars()
+            .count()
+            + 1;
+        Self::new(message, line, column)
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl From<serde_json::Error> for ParseError {
+    fn from(value: serde_json::Error) -> Self {
+        let (line, column) = (value.line(), value.column());
+        // the message of serde_json ends with the location.
+        let message = value.to_string();
+        let message = message
+            .strip_suffix(&format!(" at line {line} column {column}"))
+            .unwrap_or(&message)
+            .to_string();
+        Self::new(message, line, column)
+    }
+}
+
+#[test]
+fn parse_error_test() {
+    let error = ParseError::at_offset(String::from("oops"), "{\n  \"a\": ,\n}", 9);
+    assert_eq!(error, ParseError::new(String::from("oops"), 2, 8));
+    assert_eq!(error.to_string(), "oops at line 2 column 8");
+    let error: ParseError = serde_json::from_str::<serde_json::Value>("{\n  \"a\": ,\n}")
+        .unwrap_err()
+        .into();
+    assert_eq!(error.to_string(), "expected value at line 2 column 8");
+}
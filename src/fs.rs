@@ -1,5 +1,5 @@
 use crate::{
-    description::{DescriptionData, PkgJSON},
+    description::{parsers_key, DescriptionData, DescriptionParser, ParsersKey, PkgJSON},
     entry::EntryStat,
     tsconfig::TsConfig,
     RResult,
@@ -23,8 +23,8 @@ pub struct CachedFS {
     /// Caches raw files
     entries: CachedMap<String>,
 
-    /// Caches parsed package.json
-    descriptions: CachedMap<DescriptionData>,
+    /// Caches parsed package.json for each set of parsers
+    descriptions: DashMap<(PathBuf, ParsersKey), CachedEntry<DescriptionData>>,
 
     /// Caches tsconfig.json
     tsconfigs: CachedMap<serde_json::Value>,
@@ -79,18 +79,19 @@ impl CachedFS {
         &self,
         path: &Path,
         file_stat: EntryStat,
+        parsers: &[(String, DescriptionParser)],
     ) -> RResult<Arc<DescriptionData>> {
-        if let Some(cached) = self.descriptions.get(path) {
+        let key = (path.to_path_buf(), parsers_key(parsers));
+        if let Some(cached) = self.descriptions.get(&key) {
             if self.is_modified(file_stat.modified(), cached.stat.modified()) {
                 return Ok(cached.value().content());
             }
         }
         let string = fs::read_to_string(path)?;
-        let json = PkgJSON::parse(&string, path)?;
-        let dir = path.parent().unwrap().to_path_buf();
-        let info = DescriptionData::new(json, dir);
+        let json = PkgJSON::parse(&string, path, parsers)?;
+        let info = DescriptionData::new(json, path);
         let entry = CachedEntry::new(info, file_stat);
-        self.descriptions.insert(path.to_path_buf(), entry.clone());
+        self.descriptions.insert(key, entry.clone());
         Ok(entry.content())
     }
 
@@ -43,42 +43,58 @@
 mod cache;
 mod context;
 mod description;
+mod diagnostic;
 mod entry;
 mod error;
 mod fs;
 mod info;
 mod kind;
 mod log;
+mod manifest;
 mod map;
 mod options;
 mod parse;
 mod plugin;
 mod resolve;
 mod resource;
+mod semver;
 mod state;
+mod suggestion;
 mod tsconfig;
 mod tsconfig_path;
+mod workspace;
 
 pub use cache::Cache;
-use context::Context;
-pub use description::DescriptionData;
-pub use error::Error;
-use info::Info;
-use kind::PathKind;
+pub use context::Context;
+pub use description::{DescriptionData, DescriptionParser};
+pub use diagnostic::{Diagnostic, Severity};
+pub use error::{Error, ParseError};
+pub use info::{Info, NormalizedPath};
+pub use kind::PathKind;
 use log::{color, depth};
 use options::EnforceExtension::{Auto, Disabled, Enabled};
-pub use options::{AliasMap, EnforceExtension, Options};
+use options::DECLARATION_EXTENSIONS;
+pub use options::{
+    Alias, AliasContext, AliasKey, AliasMap, CheckLevel, DynamicAlias, EnforceExtension,
+    LifecycleHooks, ModuleKind, Options, Restriction,
+};
+pub use parse::Request;
+use path_absolutize::Absolutize;
 use plugin::{
-    AliasPlugin, BrowserFieldPlugin, ImportsFieldPlugin, ParsePlugin, Plugin, PreferRelativePlugin,
-    SymlinkPlugin,
+    AliasFieldPlugin, AliasPlugin, CaseMismatchPlugin, DependenciesPlugin, ImportsFieldPlugin,
+    ParsePlugin, Plugin, PreferRelativePlugin, RootsPlugin, SourceRedirectPlugin, SymlinkPlugin,
 };
+pub use plugin::{HookPoint, ResolverPlugin};
 pub use resource::Resource;
-use state::State;
+pub use state::State;
 
 #[derive(Debug)]
 pub struct Resolver {
     pub options: Options,
     pub(crate) cache: std::sync::Arc<Cache>,
+    pub(crate) workspace_packages: once_cell::sync::OnceCell<workspace::WorkspacePackages>,
+    pub(crate) module_manifest: once_cell::sync::OnceCell<manifest::ModuleManifest>,
+    pub(crate) global_dirs: Vec<std::path::PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -100,6 +116,12 @@ impl Resolver {
             std::sync::Arc::new(Cache::default())
         };
 
+        let options = if options.types {
+            Self::normalize_types_options(options)
+        } else {
+            options
+        };
+
         let enforce_extension = match options.enforce_extension {
             Auto => {
                 if options.extensions.iter().any(|ext| ext.is_empty()) {
@@ -111,11 +133,81 @@ impl Resolver {
             _ => options.enforce_extension,
         };
 
+        let module_lookup_boundary = options
+            .module_lookup_boundary
+            .map(|boundary| info::NormalizedPath::new(boundary).as_ref().to_path_buf());
+        let workspace = options
+            .workspace
+            .map(|root| root.absolutize().unwrap().to_path_buf());
+        let module_manifest = options
+            .module_manifest
+            .map(|manifest| manifest.absolutize().unwrap().to_path_buf());
+
         let options = Options {
             enforce_extension,
+            module_lookup_boundary,
+            workspace,
+            module_manifest,
             ..options
         };
-        Self { options, cache }
+        let global_dirs = match &options.global_dirs {
+            _ if !options.global_modules => vec![],
+            Some(global_dirs) => global_dirs.clone(),
+            None => resolve::node_global_dirs(
+                std::env::var_os("NODE_PATH"),
+                std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")),
+                std::env::var_os("PREFIX"),
+            ),
+        };
+        Self {
+            options,
+            cache,
+            workspace_packages: Default::default(),
+            module_manifest: Default::default(),
+            global_dirs,
+        }
+    }
+
+    fn normalize_types_options(options: Options) -> Options {
+        let mut extensions = options.extensions;
+        if !extensions
+            .iter()
+            .any(|ext| DECLARATION_EXTENSIONS.iter().any(|(_, dts)| ext == dts))
+        {
+            extensions = DECLARATION_EXTENSIONS
+                .iter()
+                .map(|(_, dts)| dts.to_string())
+                .collect();
+        }
+
+        let mut extension_alias = options.extension_alias;
+        for (js, dts) in DECLARATION_EXTENSIONS {
+            if !extension_alias.iter().any(|(ext, _)| ext == js) {
+                extension_alias.push((js.to_string(), vec![dts.to_string()]));
+            }
+        }
+
+        let main_fields = ["types", "typings"]
+            .into_iter()
+            .map(|field| vec![String::from(field)])
+            .chain(
+                options
+                    .main_fields
+                    .into_iter()
+                    .filter(|field| field != &["types"] && field != &["typings"]),
+            )
+            .collect();
+
+        let mut condition_names = options.condition_names;
+        condition_names.insert(String::from("types"));
+
+        Options {
+            extensions,
+            extension_alias,
+            main_fields,
+            condition_names,
+            ..options
+        }
     }
 
     pub fn resolve(
@@ -123,15 +215,28 @@ impl Resolver {
         path: &std::path::Path,
         request: &str,
     ) -> RResult<ResolveResult<Resource>> {
+        self.resolve_with_diagnostics(path, request).0
+    }
+
+    /// Same as `resolve`, but also returns the diagnostics reported
+    /// during the resolving.
+    pub fn resolve_with_diagnostics(
+        &self,
+        path: &std::path::Path,
+        request: &str,
+    ) -> (RResult<ResolveResult<Resource>>, Vec<Diagnostic>) {
         tracing::debug!(
             "{:-^30}\nTry to resolve '{}' in '{}'",
             color::green(&"[RESOLVER]"),
             color::cyan(&request),
             color::cyan(&path.display().to_string())
         );
+        if let Some(on_resolve_start) = &self.options.lifecycle_hooks.on_resolve_start {
+            on_resolve_start(path, request);
+        }
         // let start = std::time::Instant::now();
         let parsed = Self::parse(request);
-        let info = Info::new(path, parsed);
+        let info = Info::new(path, parsed.clone());
         let mut context = Context::new(
             self.options.fully_specified,
             self.options.resolve_to_context,
@@ -146,7 +251,23 @@ impl Resolver {
             type FallbackPlugin<'a> = AliasPlugin<'a>;
             FallbackPlugin::new(&self.options.fallback).apply(self, info, &mut context)
         });
+        let result = result.map_success(|info| {
+            DependenciesPlugin::new(path, &parsed).apply(self, info, &mut context)
+        });
+        let result =
+            result.map_success(|info| SourceRedirectPlugin::apply(self, info, &mut context));
+        let result = result.map_success(|info| CaseMismatchPlugin::apply(self, info, &mut context));
         let result = result.map_success(|info| SymlinkPlugin::apply(self, info, &mut context));
+        let result = result.map_success(|info| {
+            self.apply_plugins(HookPoint::Result, info, &mut context)
+                .then(|info| State::Success(ResolveResult::Resource(info)))
+        });
+        let result = match result {
+            State::Resolving(info) | State::Failed(info) => self
+                .apply_plugins(HookPoint::OnFailed, info, &mut context)
+                .then(State::Failed),
+            _ => result,
+        };
 
         // let duration = start.elapsed().as_millis();
         // println!("time cost: {:?} us", duration); // us
@@ -159,15 +280,56 @@ impl Resolver {
         //     );
         // }
 
-        match result {
+        let mut result = match result {
             State::Success(ResolveResult::Ignored) => Ok(ResolveResult::Ignored),
             State::Success(ResolveResult::Resource(info)) => {
-                let resource = Resource::new(info, self);
-                Ok(ResolveResult::Resource(resource))
+                Resource::new(info, self, &mut context).map(ResolveResult::Resource)
             }
             State::Error(err) => Err(err),
             State::Resolving(_) | State::Failed(_) => Err(Error::ResolveFailedTag),
+        };
+        if self.options.suggestions
+            && matches!(
+                result,
+                Err(Error::ResolveFailedTag | Error::UnexpectedValue(_))
+            )
+        {
+            let suggestions = self.suggest(path, request, &mut context);
+            if !suggestions.is_empty() {
+                let candidates = suggestions
+                    .iter()
+                    .map(|(suggestion, _)| suggestion.clone())
+                    .collect();
+                if let Err(err) = result {
+                    result = Err(Error::Suggested((Box::new(err), candidates)));
+                }
+            }
+            context
+                .diagnostics
+                .extend(
+                    suggestions
+                        .into_iter()
+                        .map(|(suggestion, location)| Diagnostic {
+                            severity: Severity::Hint,
+                            message: format!("Did you mean '{suggestion}'?"),
+                            location,
+                            key_path: vec![],
+                        }),
+                );
         }
+        let hooks = &self.options.lifecycle_hooks;
+        match (&result, &hooks.on_resolved, &hooks.on_failed) {
+            (Ok(ResolveResult::Resource(resource)), Some(on_resolved), _) => on_resolved(resource),
+            (Err(err), _, Some(on_failed)) => on_failed(err),
+            _ => {}
+        }
+        (result, context.diagnostics)
+    }
+
+    /// Resolve `info` in a `ResolverPlugin`, the `context` should be
+    /// the one passed to the plugin.
+    pub fn resolve_info(&self, info: Info, context: &mut Context) -> State {
+        self._resolve(info, context)
     }
 
     fn _resolve(&self, info: Info, context: &mut Context) -> State {
@@ -182,37 +344,54 @@ impl Resolver {
             return State::Error(Error::Overflow);
         }
 
-        let state = ParsePlugin::apply(self, info, context)
+        // the restrictions reject the resolved path with the original request,
+        // so that the other candidates and `fallback` can still be tried.
+        let original = info.clone();
+        let state = self
+            .apply_plugins(HookPoint::BeforeResolve, info, context)
+            .then(|info| ParsePlugin::apply(self, info, context))
             .then(|info| AliasPlugin::new(&self.options.alias).apply(self, info, context))
             .then(|info| PreferRelativePlugin::apply(self, info, context))
+            .then(|info| self.apply_plugins(HookPoint::AfterAlias, info, context))
             .then(|info| {
                 let request = info.to_resolved_path();
                 let entry = self.load_entry(&request);
-                let pkg_info = match entry.pkg_info(self) {
+                let pkg_info = match entry.pkg_info(self, context) {
                     Ok(pkg_info) => pkg_info,
                     Err(error) => return State::Error(error),
                 };
                 if let Some(pkg_info) = pkg_info {
-                    ImportsFieldPlugin::new(pkg_info)
+                    ImportsFieldPlugin::new(&pkg_info)
                         .apply(self, info, context)
                         .then(|info| {
-                            BrowserFieldPlugin::new(pkg_info, false).apply(self, info, context)
+                            AliasFieldPlugin::new(&pkg_info, false).apply(self, info, context)
                         })
                 } else {
                     State::Resolving(info)
                 }
             })
+            .then(|info| self.apply_plugins(HookPoint::DescribedResolve, info, context))
             .then(|info| {
                 if matches!(
                     info.request().kind(),
                     PathKind::AbsolutePosix | PathKind::AbsoluteWin | PathKind::Relative
                 ) {
-                    self.resolve_as_context(info, context)
-                        .then(|info| self.resolve_as_fully_specified(info, context))
-                        .then(|info| self.resolve_as_file(info, context))
-                        .then(|info| self.resolve_as_dir(info, context))
+                    RootsPlugin::apply(self, info, context)
+                } else {
+                    self.apply_plugins(HookPoint::BeforeModuleLookup, info, context)
+                        .then(|info| self.resolve_as_modules(info, context))
+                }
+            })
+            .map_success(|info| {
+                if self.is_satisfied_restrictions(&info, context) {
+                    State::Success(ResolveResult::Resource(info))
                 } else {
-                    self.resolve_as_modules(info, context)
+                    tracing::debug!(
+                        "'{}' is not satisfied with restrictions({})",
+                        color::blue(&info.normalized_path().as_ref().display()),
+                        depth(&context.depth)
+                    );
+                    State::Failed(original)
                 }
             });
 
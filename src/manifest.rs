@@ -0,0 +1,159 @@
+use crate::{
+    log::color, resolve::get_module_name_from_request, Context, Error, Info, RResult, Resolver,
+    State,
+};
+use path_absolutize::Absolutize;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// The content of `module_manifest`. All the paths are relative to the
+/// directory of the manifest file.
+///
+/// ```json
+/// {
+///   "packages": { "foo": "./pkgs/foo" },
+///   "scopes": { "./app/legacy": { "foo": "./pkgs/foo-v1" } }
+/// }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+struct RawModuleManifest {
+    #[serde(default)]
+    packages: FxHashMap<String, PathBuf>,
+    #[serde(default)]
+    scopes: FxHashMap<String, FxHashMap<String, PathBuf>>,
+}
+
+#[derive(Debug, Default)]
+pub struct ModuleManifest {
+    packages: FxHashMap<String, PathBuf>,
+    /// Sorted by the length of importer prefix, the longest is the first.
+    scopes: Vec<(PathBuf, FxHashMap<String, PathBuf>)>,
+}
+
+impl ModuleManifest {
+    fn new(raw: RawModuleManifest, dir: &Path) -> Self {
+        let resolve_paths = |packages: FxHashMap<String, PathBuf>| {
+            packages
+                .into_iter()
+                .map(|(name, pkg_dir)| (name, normalize(&dir.join(pkg_dir))))
+                .collect::<FxHashMap<_, _>>()
+        };
+        let mut scopes = raw
+            .scopes
+            .into_iter()
+            .map(|(prefix, packages)| (normalize(&dir.join(prefix)), resolve_paths(packages)))
+            .collect::<Vec<_>>();
+        scopes.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.components().count()));
+        Self {
+            packages: resolve_paths(raw.packages),
+            scopes,
+        }
+    }
+
+    /// Returns the directory of package `name` which imported from `importer`.
+    fn lookup(&self, importer: &Path, name: &str) -> Option<&PathBuf> {
+        self.scopes
+            .iter()
+            .filter(|(prefix, _)| importer.starts_with(prefix))
+            .find_map(|(_, packages)| packages.get(name))
+            .or_else(|| self.packages.get(name))
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.absolutize_from(Path::new("")).unwrap().to_path_buf()
+}
+
+impl Resolver {
+    fn module_manifest(&self) -> RResult<Option<&ModuleManifest>> {
+        let Some(manifest_path) = self.options.module_manifest.as_ref() else {
+            return Ok(None);
+        };
+        self.module_manifest
+            .get_or_try_init(|| {
+                let entry = self.load_entry(manifest_path);
+                if !entry.is_file() {
+                    return Err(Error::UnexpectedValue(format!(
+                        "Can't find module manifest {}",
+                        manifest_path.display()
+                    )));
+                }
+                let content = self
+                    .cache
+                    .fs
+                    .read_file(manifest_path, entry.cached_stat())?;
+                let raw: RawModuleManifest = serde_json::from_str(&content).map_err(|error| {
+                    Error::UnexpectedJson((manifest_path.as_path().into(), error.into()))
+                })?;
+                let dir = manifest_path.parent().unwrap_or(Path::new(""));
+                Ok(ModuleManifest::new(raw, dir))
+            })
+            .map(Some)
+    }
+
+    /// Resolve the bare specifier from `module_manifest`. Once the manifest
+    /// is provided, the `node_modules` will never be looked up.
+    pub(crate) fn resolve_manifest_package(&self, info: Info, context: &mut Context) -> State {
+        let manifest = match self.module_manifest() {
+            Ok(Some(manifest)) => manifest,
+            Ok(None) => return State::Resolving(info),
+            Err(err) => return State::Error(err),
+        };
+        let module_name = get_module_name_from_request(info.request().target());
+        let Some(pkg_dir) = manifest.lookup(info.normalized_path().as_ref(), module_name) else {
+            tracing::debug!(
+                "'{}' is not listed in module manifest",
+                color::blue(&module_name)
+            );
+            return State::Failed(info);
+        };
+        tracing::debug!(
+            "Resolving '{}' from module manifest, package located at '{}'",
+            color::blue(&info.request().target()),
+            color::blue(&pkg_dir.display())
+        );
+        match self.resolve_package_dir(info, pkg_dir, context) {
+            State::Resolving(info) => State::Failed(info),
+            state => state,
+        }
+    }
+}
+
+#[test]
+fn manifest_lookup_test() {
+    let raw: RawModuleManifest = serde_json::from_str(
+        r#"{
+            "packages": { "foo": "./foo", "bar": "bar" },
+            "scopes": {
+                "./app": { "foo": "./foo-app" },
+                "./app/legacy": { "foo": "./foo-legacy" }
+            }
+        }"#,
+    )
+    .unwrap();
+    let manifest = ModuleManifest::new(raw, Path::new("/root"));
+    let lookup = |importer: &str, name: &str| {
+        manifest
+            .lookup(Path::new(importer), name)
+            .map(|path| path.to_path_buf())
+    };
+    assert_eq!(lookup("/root/src", "foo"), Some(PathBuf::from("/root/foo")));
+    assert_eq!(
+        lookup("/root/app/src", "foo"),
+        Some(PathBuf::from("/root/foo-app"))
+    );
+    assert_eq!(
+        lookup("/root/app/legacy/src", "foo"),
+        Some(PathBuf::from("/root/foo-legacy"))
+    );
+    assert_eq!(
+        lookup("/root/application", "foo"),
+        Some(PathBuf::from("/root/foo"))
+    );
+    assert_eq!(
+        lookup("/root/app/src", "bar"),
+        Some(PathBuf::from("/root/bar"))
+    );
+    assert_eq!(lookup("/root/app/src", "baz"), None);
+}
@@ -7,6 +7,14 @@ type ConditionalMapping = serde_json::Map<String, MappingValue>;
 
 pub struct ImportsField;
 
+/// The targets mapped by the key which matches the request.
+#[derive(Debug, Default)]
+pub struct FieldMapping {
+    pub targets: Vec<String>,
+    /// The matched key if it is a deprecated folder mapping, such as `"./lib/"`.
+    pub folder_key: Option<String>,
+}
+
 pub struct ExportsField;
 
 const DEFAULT_MARK: &str = "default";
@@ -164,18 +172,21 @@ pub trait Field {
         root: &'a serde_json::Value,
         target: &'a str,
         condition_names: &'a HashSet<String>,
-    ) -> RResult<Vec<String>> {
+    ) -> RResult<FieldMapping> {
         let request = Self::assert_request(target)?;
         let Some((mapping, remaining_request, is_subpath_mapping, is_pattern)) = Self::find_match(root, &request)? else {
-            return Ok(vec![])
+            return Ok(FieldMapping::default())
         };
-        Self::mapping(
+        let targets = Self::mapping(
             remaining_request,
             is_pattern,
             is_subpath_mapping,
             mapping,
             condition_names,
-        )
+        )?;
+        let folder_key = is_subpath_mapping
+            .then(|| request[..request.len() - remaining_request.len()].to_string());
+        Ok(FieldMapping { targets, folder_key })
     }
 }
 
@@ -435,6 +446,7 @@ mod exports_field_map_test {
         condition_names: Vec<&str>,
     ) -> RResult<Vec<String>> {
         ExportsField::field_process(&value, request, &test_helper::vec_to_set(condition_names))
+            .map(|mapping| mapping.targets)
     }
 
     fn should_equal(
@@ -2153,6 +2165,7 @@ fn imports_field_map_test() {
         condition_names: Vec<&str>,
     ) -> RResult<Vec<String>> {
         ImportsField::field_process(&value, request, &test_helper::vec_to_set(condition_names))
+            .map(|mapping| mapping.targets)
     }
 
     fn should_equal(
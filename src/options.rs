@@ -1,11 +1,68 @@
-use std::{collections::HashSet, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use crate::Cache;
+use crate::description::default_description_parsers;
+use crate::{Cache, DescriptionData, DescriptionParser, Error, ResolverPlugin, Resource};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum AliasMap {
     Target(String),
     Ignored,
+    /// Decides the targets by the importer, the targets it returned are
+    /// used as the request directly, and `None` means skipping it.
+    Dynamic(DynamicAlias),
+}
+
+/// The information passed to `AliasMap::Dynamic`.
+#[derive(Debug)]
+pub struct AliasContext<'a> {
+    /// The target of request, without query and fragment.
+    pub request: &'a str,
+    /// The directory of importer.
+    pub importer: &'a Path,
+    /// The description file which the importer belongs to.
+    pub description: Option<&'a DescriptionData>,
+}
+
+type DynamicAliasFn = dyn Fn(&AliasContext) -> Option<Vec<AliasMap>> + Send + Sync;
+
+#[derive(Clone)]
+pub struct DynamicAlias(Arc<DynamicAliasFn>);
+
+impl DynamicAlias {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&AliasContext) -> Option<Vec<AliasMap>> + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    pub(crate) fn call(&self, context: &AliasContext) -> Option<Vec<AliasMap>> {
+        (self.0)(context)
+    }
+}
+
+impl std::fmt::Debug for DynamicAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DynamicAlias({:p})", Arc::as_ptr(&self.0))
+    }
+}
+
+impl PartialEq for DynamicAlias {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for DynamicAlias {}
+
+impl std::hash::Hash for DynamicAlias {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.0) as *const () as usize).hash(state);
+    }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -15,7 +72,134 @@ pub enum EnforceExtension {
     Auto,
 }
 
-pub type Alias = Vec<(String, Vec<AliasMap>)>;
+/// How to deal with the problem found by the optional checks, such as
+/// `case_mismatch`, `phantom_dependencies` and `version_mismatch`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum CheckLevel {
+    Ignore,
+    /// Report a warning diagnostic.
+    Report,
+    /// Return an error.
+    Fail,
+}
+
+/// The format of the importing file, which decides whether `"import"`
+/// or `"require"` condition is used by the `node16` and `nodenext` presets.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ModuleKind {
+    CommonJs,
+    EsModule,
+}
+
+/// The condition that the resolved path must satisfy.
+#[derive(Debug, Clone)]
+pub enum Restriction {
+    /// The resolved path must be located in this directory.
+    Path(PathBuf),
+    /// The resolved path must match this pattern.
+    Regex(regex::Regex),
+}
+
+/// The key of `alias` and `fallback`.
+#[derive(Debug, Clone)]
+pub enum AliasKey {
+    /// Matches the request which equals to it or starts with it and `/`, and
+    /// only the request which equals to it when it ends with `$`.
+    /// If it contains a `*`, such as `@/*`, the `*` matches any characters
+    /// and replaces the `*` in targets.
+    String(String),
+    /// Matches the request with the pattern, the `$1` and `${name}` in
+    /// targets are replaced by the captured groups.
+    Regex(regex::Regex),
+}
+
+impl From<String> for AliasKey {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for AliasKey {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<regex::Regex> for AliasKey {
+    fn from(value: regex::Regex) -> Self {
+        Self::Regex(value)
+    }
+}
+
+impl std::fmt::Display for AliasKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AliasKey::String(key) => write!(f, "{key}"),
+            AliasKey::Regex(regex) => write!(f, "/{regex}/"),
+        }
+    }
+}
+
+/// The list of `alias` and `fallback`, it can be converted from the list
+/// whose keys are `String`, `&str`, `Regex` or `AliasKey`.
+#[derive(Debug, Clone, Default)]
+pub struct Alias(Vec<(AliasKey, Vec<AliasMap>)>);
+
+impl Alias {
+    pub fn iter(&self) -> std::slice::Iter<'_, (AliasKey, Vec<AliasMap>)> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<K: Into<AliasKey>> From<Vec<(K, Vec<AliasMap>)>> for Alias {
+    fn from(value: Vec<(K, Vec<AliasMap>)>) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(key, targets)| (key.into(), targets))
+                .collect(),
+        )
+    }
+}
+
+pub type OnResolveStart = Arc<dyn Fn(&Path, &str) + Send + Sync>;
+pub type OnResolved = Arc<dyn Fn(&Resource) + Send + Sync>;
+pub type OnFailed = Arc<dyn Fn(&Error) + Send + Sync>;
+pub type OnFileProbe = Arc<dyn Fn(&Path, bool) + Send + Sync>;
+
+/// The read-only callbacks to observe the resolving.
+#[derive(Clone, Default)]
+pub struct LifecycleHooks {
+    /// Called with the directory and request when `Resolver::resolve` starts.
+    pub on_resolve_start: Option<OnResolveStart>,
+    /// Called when the request is resolved to a resource.
+    pub on_resolved: Option<OnResolved>,
+    /// Called when `Resolver::resolve` returns an error.
+    pub on_failed: Option<OnFailed>,
+    /// Called with the path and whether it exists when an entry is probed
+    /// for the first time, includes the files, directories and description
+    /// files. The cached entries are not reported again.
+    pub on_file_probe: Option<OnFileProbe>,
+}
+
+impl std::fmt::Debug for LifecycleHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LifecycleHooks")
+            .field("on_resolve_start", &self.on_resolve_start.is_some())
+            .field("on_resolved", &self.on_resolved.is_some())
+            .field("on_failed", &self.on_failed.is_some())
+            .field("on_file_probe", &self.on_file_probe.is_some())
+            .finish()
+    }
+}
+
+/// Maps the extension of javascript file to its declaration file.
+pub(crate) const DECLARATION_EXTENSIONS: [(&str, &str); 3] =
+    [(".js", ".d.ts"), (".mjs", ".d.mts"), (".cjs", ".d.cts")];
 
 #[derive(Debug, Clone)]
 pub struct Options {
@@ -27,7 +211,11 @@ pub struct Options {
     pub enforce_extension: EnforceExtension,
     /// Maps key to value.
     /// Default is `vec![]`.
-    /// The reason for using `Vec` instead `HashMap` to keep the order.
+    /// The keys are tried in order, that is the reason for using `Vec` instead
+    /// `HashMap`. Among the keys with `*` and the regex keys, the one with the
+    /// longest literal part is tried first, and the regex keys are the last.
+    /// A key is not applied to the request rewritten by itself, and rewriting
+    /// through several keys back to the same key ends with `Error::Overflow`.
     pub alias: Alias,
     /// Prefer to resolve request as relative request and
     /// fallback to resolving as modules.
@@ -45,22 +233,59 @@ pub struct Options {
     /// is a symlink.
     /// Default is `true`.
     pub symlinks: bool,
-    /// A JSON file to describing this lib information.
-    /// Default is `"package.json"`.
-    pub description_file: String,
+    /// Files to describing this lib information, they are
+    /// tried in order in each directory.
+    /// Default is `["package.json"]`.
+    pub description_files: Vec<String>,
+    /// The parsers of description files, keyed by the file extension.
+    /// Default is parsing `json`, `json5`, `yaml` and `yml`.
+    pub description_parsers: Vec<(String, DescriptionParser)>,
+    /// Whether to treat the malformed description files as absent,
+    /// they are reported by warnings instead of failing the resolving.
+    /// Default is `false`.
+    pub ignore_invalid_description_files: bool,
+    /// Whether to find the "did you mean" candidates, such as the similar
+    /// files or packages, when the resolving failed. They are attached to
+    /// the error by `Error::Suggested` and reported as hints.
+    /// Default is `false`.
+    pub suggestions: bool,
+    /// Whether to check the case of each component in resolved path
+    /// with the directory listing, the mismatch only happens on the
+    /// case-insensitive file system.
+    /// Default is `CheckLevel::Ignore`.
+    pub case_mismatch: CheckLevel,
+    /// Whether to check the package resolved from `node_modules` by a bare
+    /// specifier is declared in the dependencies of the importer's
+    /// description file, it only works because of hoisting if not.
+    /// Default is `CheckLevel::Ignore`.
+    pub phantom_dependencies: CheckLevel,
+    /// Whether to check the `version` of the package resolved from
+    /// `node_modules` satisfies the range declared in the dependencies
+    /// of the importer's description file, the mismatch is usually caused
+    /// by the stale installs or the wrong hoisting.
+    /// Default is `CheckLevel::Ignore`.
+    pub version_mismatch: CheckLevel,
+    /// Whether to keep probing after the first matched candidate, such as
+    /// the file with other extensions, the other main files or the same package
+    /// in the other lookup paths, and report the ones which are shadowed.
+    /// Default is `false`.
+    pub detect_ambiguity: bool,
     /// Resolve to a context instead of a file.
     /// Default is `false`
     pub resolve_to_context: bool,
     /// Main file in this directory.
     /// Default is `["index"]`.
     pub main_files: Vec<String>,
-    /// Main fields in Description.
-    /// Default is `["main"]`.
-    pub main_fields: Vec<String>,
-    /// Whether read and parse `"browser"` filed
-    /// in package.json.
-    /// Default is `false`
-    pub browser_field: bool,
+    /// Main fields in Description, each one is a key path such as
+    /// `["publishConfig", "main"]`. An object value uses its `"."` entry,
+    /// such as the object form of `browser`.
+    /// Default is `[["main"]]`.
+    pub main_fields: Vec<Vec<String>>,
+    /// A list of alias fields in description files, such as `[["browser"]]`,
+    /// which follow the spec of `"browser"` field:
+    /// <https://github.com/defunctzombie/package-browser-field-spec>.
+    /// Default is `[]`.
+    pub alias_fields: Vec<Vec<String>>,
     /// Condition names for exports filed. Note that its type is a `HashSet`,
     /// because the priority is related to the order in which the export field
     /// fields are written.
@@ -84,9 +309,79 @@ pub struct Options {
     /// A list of exports fields in descriptions files
     /// Default is `[["exports"]]`.
     pub exports_field: Vec<Vec<String>>,
+    /// A list of imports fields in descriptions files, the first one
+    /// which exists is used.
+    /// Default is `[["imports"]]`.
+    pub imports_field: Vec<Vec<String>>,
     /// A vector which maps extension to extension aliases.
     /// Default is `[]`.
     pub extension_alias: Vec<(String, Vec<String>)>,
+    /// Resolve the request to its TypeScript declaration file.
+    /// When it is enabled, `types` and `typings` are tried before `main_fields`,
+    /// `"types"` is added to `condition_names`, `typesVersions` in package.json
+    /// is applied and `node_modules/@types` is used as fallback.
+    /// `extension_alias` is applied to the fully specified requests as well,
+    /// such as `./foo.js` to `./foo.ts` in ES module.
+    /// If `extensions` contains no declaration extension, it will be replaced
+    /// with `[".d.ts", ".d.mts", ".d.cts"]`.
+    /// Default is `false`.
+    pub types: bool,
+    /// The TypeScript version used to select the entry of `typesVersions`.
+    /// Default is `"5.3"`.
+    pub typescript_version: String,
+    /// Redirect the build output of a package which is reached through symlinks,
+    /// such as a workspace package, to its TypeScript source. The `outDir`,
+    /// `declarationDir` and `rootDir` are read from the `tsconfig.json` located
+    /// in the package, and `rootDir` defaults to the directory of that file.
+    /// Default is `false`.
+    pub redirect_to_source: bool,
+    /// The root directory of a workspace. The packages listed in its
+    /// `pnpm-workspace.yaml` or the `workspaces` field of its description file
+    /// are resolved from their directories directly instead of `node_modules`.
+    /// A relative root is resolved against the current working directory.
+    /// Default is `None`.
+    pub workspace: Option<PathBuf>,
+    /// The path of a JSON manifest which maps the package name to its directory,
+    /// the `scopes` field overrides the mapping for importers under a directory:
+    /// `{ "packages": { "foo": "./foo" }, "scopes": { "./app": { "foo": "./foo-v2" } } }`.
+    /// Paths are relative to the manifest. Once provided, the bare specifiers are only
+    /// resolved from the manifest and `node_modules` will not be looked up.
+    /// A relative manifest path is resolved against the current working directory.
+    /// Default is `None`.
+    pub module_manifest: Option<PathBuf>,
+    /// Whether to look up the global folders of Node.js after the `modules`
+    /// are exhausted, in the order of `NODE_PATH` entries, `$HOME/.node_modules`,
+    /// `$HOME/.node_libraries` and `$PREFIX/lib/node`.
+    /// Default is `false`.
+    pub global_modules: bool,
+    /// The global folders used by `global_modules` instead of the ones
+    /// read from the environment variables.
+    /// Default is `None`.
+    pub global_dirs: Option<Vec<PathBuf>>,
+    /// The directory where the lookup of `modules` stops, the `modules` in
+    /// its ancestors will not be looked up. It only takes effect on the
+    /// request which is resolved inside of this directory.
+    /// Default is `None`.
+    pub module_lookup_boundary: Option<PathBuf>,
+    /// A list of directories where the server-relative requests, which start
+    /// with `/`, are resolved from.
+    /// Default is `vec![]`.
+    pub roots: Vec<PathBuf>,
+    /// Try the server-relative requests as absolute paths before `roots`.
+    /// Default is `false`.
+    pub prefer_absolute: bool,
+    /// A list of restrictions which the resolved path must satisfy, otherwise
+    /// the other candidates and `fallback` will be tried. The real path is
+    /// checked if `symlinks` is enabled.
+    /// Default is `vec![]`.
+    pub restrictions: Vec<Restriction>,
+    /// The plugins which are applied at the `HookPoint`s of the pipeline.
+    /// Default is `vec![]`.
+    pub plugins: Vec<Arc<dyn ResolverPlugin>>,
+    /// The callbacks which are called during the resolving, they can't
+    /// change the result.
+    /// Default is no callbacks.
+    pub lifecycle_hooks: LifecycleHooks,
 }
 
 impl Default for Options {
@@ -97,11 +392,18 @@ impl Default for Options {
             String::from(".node"),
         ];
         let main_files = vec![String::from("index")];
-        let main_fields = vec![String::from("main")];
-        let description_file = String::from("package.json");
-        let alias = vec![];
+        let main_fields = vec![vec![String::from("main")]];
+        let description_files = vec![String::from("package.json")];
+        let description_parsers = default_description_parsers();
+        let ignore_invalid_description_files = false;
+        let suggestions = false;
+        let case_mismatch = CheckLevel::Ignore;
+        let phantom_dependencies = CheckLevel::Ignore;
+        let version_mismatch = CheckLevel::Ignore;
+        let detect_ambiguity = false;
+        let alias = Alias::default();
         let symlinks = true;
-        let browser_field = false;
+        let alias_fields = vec![];
         let condition_names = HashSet::default();
         let prefer_relative = false;
         let enforce_extension = EnforceExtension::Auto;
@@ -109,10 +411,24 @@ impl Default for Options {
         let external_cache = None;
         let resolve_to_context = false;
         let modules = vec![String::from("node_modules")];
-        let fallback = vec![];
+        let fallback = Alias::default();
         let fully_specified = false;
         let exports_field = vec![vec![String::from("exports")]];
+        let imports_field = vec![vec![String::from("imports")]];
         let extension_alias = vec![];
+        let types = false;
+        let typescript_version = String::from("5.3");
+        let redirect_to_source = false;
+        let workspace = None;
+        let module_manifest = None;
+        let global_modules = false;
+        let global_dirs = None;
+        let module_lookup_boundary = None;
+        let roots = vec![];
+        let prefer_absolute = false;
+        let restrictions = vec![];
+        let plugins = vec![];
+        let lifecycle_hooks = LifecycleHooks::default();
         Self {
             fallback,
             modules,
@@ -122,16 +438,95 @@ impl Default for Options {
             prefer_relative,
             external_cache,
             symlinks,
-            description_file,
+            description_files,
+            description_parsers,
+            ignore_invalid_description_files,
+            suggestions,
+            case_mismatch,
+            phantom_dependencies,
+            version_mismatch,
+            detect_ambiguity,
             resolve_to_context,
             main_files,
             main_fields,
-            browser_field,
+            alias_fields,
             condition_names,
             tsconfig,
             fully_specified,
             exports_field,
+            imports_field,
             extension_alias,
+            types,
+            typescript_version,
+            redirect_to_source,
+            workspace,
+            module_manifest,
+            global_modules,
+            global_dirs,
+            module_lookup_boundary,
+            roots,
+            prefer_absolute,
+            restrictions,
+            plugins,
+            lifecycle_hooks,
+        }
+    }
+}
+
+impl Options {
+    /// Replicates `"moduleResolution": "node10"` of TypeScript,
+    /// the `exports` and `imports` field are ignored.
+    #[must_use]
+    pub fn node10() -> Self {
+        Self {
+            exports_field: vec![],
+            imports_field: vec![],
+            ..Self::typescript(&[])
+        }
+    }
+
+    /// Replicates `"moduleResolution": "node16"` of TypeScript.
+    /// Relative requests in ES module must be fully specified.
+    #[must_use]
+    pub fn node16(kind: ModuleKind) -> Self {
+        match kind {
+            ModuleKind::CommonJs => Self::typescript(&["node", "require"]),
+            ModuleKind::EsModule => Self {
+                fully_specified: true,
+                ..Self::typescript(&["node", "import"])
+            },
+        }
+    }
+
+    /// Replicates `"moduleResolution": "nodenext"` of TypeScript,
+    /// which is the same as `node16` for now.
+    #[must_use]
+    pub fn nodenext(kind: ModuleKind) -> Self {
+        Self::node16(kind)
+    }
+
+    /// Replicates `"moduleResolution": "bundler"` of TypeScript.
+    #[must_use]
+    pub fn bundler() -> Self {
+        Self::typescript(&["import"])
+    }
+
+    fn typescript(condition_names: &[&str]) -> Self {
+        let to_strings = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+        let extensions = to_strings(&[".ts", ".tsx", ".d.ts"]);
+        let extension_alias = vec![
+            (String::from(".js"), to_strings(&[".ts", ".tsx", ".d.ts"])),
+            (String::from(".jsx"), to_strings(&[".tsx", ".d.ts"])),
+            (String::from(".mjs"), to_strings(&[".mts", ".d.mts"])),
+            (String::from(".cjs"), to_strings(&[".cts", ".d.cts"])),
+        ];
+        let condition_names = condition_names.iter().map(|s| s.to_string()).collect();
+        Self {
+            extensions,
+            extension_alias,
+            condition_names,
+            types: true,
+            ..Default::default()
         }
     }
 }
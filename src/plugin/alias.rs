@@ -1,5 +1,9 @@
 use super::Plugin;
-use crate::{log::depth, options::Alias, AliasMap, Context, Info, ResolveResult, Resolver, State};
+use crate::{
+    log::depth, options::Alias, AliasContext, AliasKey, AliasMap, Context, DynamicAlias, Error,
+    Info, ResolveResult, Resolver, State,
+};
+use std::cmp::Reverse;
 
 pub struct AliasPlugin<'a>(&'a Alias);
 
@@ -11,73 +15,211 @@ impl<'a> AliasPlugin<'a> {
     fn alias(&self) -> &Alias {
         self.0
     }
-}
 
-impl<'a> Plugin for AliasPlugin<'a> {
-    fn apply(&self, resolver: &Resolver, info: Info, context: &mut Context) -> State {
+    fn resolve_target(
+        resolver: &Resolver,
+        info: &Info,
+        normalized_target: &str,
+        context: &mut Context,
+    ) -> State {
+        let old_request = info.request();
+        let old_query = old_request.query();
+        let old_fragment = old_request.fragment();
+        let request = Resolver::parse(normalized_target);
+        let request = match (request.query().is_empty(), request.fragment().is_empty()) {
+            (true, true) => request.with_query(old_query).with_fragment(old_fragment),
+            (true, false) => request.with_query(old_query),
+            (false, true) => request.with_fragment(old_fragment),
+            (false, false) => request,
+        };
+        let alias_info = info.clone().with_request(request);
+        let fully_specified = context.fully_specified.get();
+        if fully_specified {
+            context.fully_specified.set(false);
+        }
+        let state = resolver._resolve(alias_info, context);
+        if fully_specified {
+            context.fully_specified.set(true);
+        }
+        state
+    }
+
+    /// Resolve the targets of the hit key, `in_cycle` means the key has
+    /// rewritten the request before, so rewriting it again is a cycle.
+    fn resolve_hit(
+        resolver: &Resolver,
+        info: &Info,
+        hit: &Hit,
+        array: &[AliasMap],
+        in_cycle: bool,
+        context: &mut Context,
+    ) -> State {
         let inner_target = info.request().target();
-        for (from, array) in self.alias() {
-            let only_module = from.ends_with('$');
-            let from_to = from.len();
-            let (hit, key) = if only_module {
-                let sub = &from[0..from_to - 1];
-                if inner_target.eq(sub) {
-                    (true, sub)
-                } else {
-                    (false, sub)
+        for to in array {
+            let state = match to {
+                AliasMap::Target(to) => match hit.replace(inner_target, to) {
+                    Some(_) if in_cycle => return State::Error(Error::Overflow),
+                    Some(normalized_target) => {
+                        Self::resolve_target(resolver, info, &normalized_target, context)
+                    }
+                    None => continue,
+                },
+                AliasMap::Ignored => return State::Success(ResolveResult::Ignored),
+                AliasMap::Dynamic(_) if in_cycle => return State::Error(Error::Overflow),
+                AliasMap::Dynamic(dynamic) => {
+                    Self::resolve_dynamic(resolver, info, dynamic, context)
                 }
-            } else {
-                let hit = inner_target
-                    .strip_prefix(from)
-                    .into_iter()
-                    .next()
-                    .map_or(false, |c| c.is_empty() || c.starts_with('/'));
-                (hit, from.as_str())
             };
-            if hit {
-                tracing::debug!(
-                    "AliasPlugin works, triggered by '{from}'({})",
-                    depth(&context.depth)
-                );
-                for to in array {
-                    match to {
-                        AliasMap::Target(to) => {
-                            if inner_target.starts_with(to) {
-                                // skip `target.starts_with(to)` to prevent infinite loop.
-                                continue;
-                            }
-                            let normalized_target = inner_target.replacen(key, to, 1);
-                            let old_request = info.request();
-                            let old_query = old_request.query();
-                            let old_fragment = old_request.fragment();
-                            let request = Resolver::parse(&normalized_target);
-                            let request =
-                                match (request.query().is_empty(), request.fragment().is_empty()) {
-                                    (true, true) => {
-                                        request.with_query(old_query).with_fragment(old_fragment)
-                                    }
-                                    (true, false) => request.with_query(old_query),
-                                    (false, true) => request.with_fragment(old_fragment),
-                                    (false, false) => request,
-                                };
-                            let alias_info = info.clone().with_request(request);
-                            let fully_specified = context.fully_specified.get();
-                            if fully_specified {
-                                context.fully_specified.set(false);
-                            }
-                            let state = resolver._resolve(alias_info, context);
-                            if fully_specified {
-                                context.fully_specified.set(true);
-                            }
-                            if state.is_finished() {
-                                return state;
-                            }
-                        }
-                        AliasMap::Ignored => return State::Success(ResolveResult::Ignored),
+            if state.is_finished() {
+                return state;
+            }
+        }
+        State::Resolving(info.clone())
+    }
+
+    fn resolve_dynamic(
+        resolver: &Resolver,
+        info: &Info,
+        dynamic: &DynamicAlias,
+        context: &mut Context,
+    ) -> State {
+        let inner_target = info.request().target();
+        let importer = info.normalized_path().as_ref();
+        let entry = resolver.load_entry(importer);
+        let pkg_info = match entry.pkg_info(resolver, context) {
+            Ok(pkg_info) => pkg_info,
+            Err(err) => return State::Error(err),
+        };
+        let alias_context = AliasContext {
+            request: inner_target,
+            importer,
+            description: pkg_info.as_deref(),
+        };
+        for to in dynamic.call(&alias_context).into_iter().flatten() {
+            match to {
+                AliasMap::Target(to) => {
+                    if to == inner_target {
+                        continue;
+                    }
+                    let state = Self::resolve_target(resolver, info, &to, context);
+                    if state.is_finished() {
+                        return state;
                     }
                 }
-                tracing::debug!("Leaving AliasPlugin({})", depth(&context.depth));
+                AliasMap::Ignored => return State::Success(ResolveResult::Ignored),
+                // nested dynamic alias is not supported.
+                AliasMap::Dynamic(_) => {}
+            }
+        }
+        State::Resolving(info.clone())
+    }
+}
+
+/// How the request hits the key of alias.
+enum Hit<'a> {
+    /// The request equals to `key` or starts with `key/`.
+    Prefix(&'a str),
+    /// The part of the request matched by `*`.
+    Star(&'a str),
+    Captures(regex::Captures<'a>),
+}
+
+impl<'a> Hit<'a> {
+    fn new(key: &'a AliasKey, target: &'a str) -> Option<Self> {
+        match key {
+            AliasKey::String(key) => {
+                if let Some((prefix, suffix)) = key.split_once('*') {
+                    let suffix = suffix.strip_suffix('$').unwrap_or(suffix);
+                    target
+                        .strip_prefix(prefix)
+                        .and_then(|rest| rest.strip_suffix(suffix))
+                        .map(Hit::Star)
+                } else if let Some(key) = key.strip_suffix('$') {
+                    target.eq(key).then_some(Hit::Prefix(key))
+                } else {
+                    target
+                        .strip_prefix(key.as_str())
+                        .map_or(false, |c| c.is_empty() || c.starts_with('/'))
+                        .then_some(Hit::Prefix(key))
+                }
+            }
+            AliasKey::Regex(regex) => regex.captures(target).map(Hit::Captures),
+        }
+    }
+
+    /// The precedence among the pattern keys, the string key with longer
+    /// literal part is the first, and the regex key is the last.
+    /// `None` for the plain keys, which keep the order of declaration.
+    fn precedence(&self, key: &AliasKey) -> Option<Option<usize>> {
+        match (self, key) {
+            (Hit::Prefix(_), _) => None,
+            (Hit::Star(_), AliasKey::String(key)) => {
+                Some(Some(key.trim_end_matches('$').len() - 1))
+            }
+            _ => Some(None),
+        }
+    }
+
+    /// Returns the new target, or `None` if the target is pointed to itself.
+    fn replace(&self, target: &str, to: &str) -> Option<String> {
+        let replaced = match self {
+            Hit::Prefix(key) => {
+                if target.starts_with(to) {
+                    // skip `target.starts_with(to)` to prevent infinite loop.
+                    return None;
+                }
+                target.replacen(key, to, 1)
+            }
+            Hit::Star(star) => to.replace('*', star),
+            Hit::Captures(captures) => {
+                let mut replaced = String::new();
+                captures.expand(to, &mut replaced);
+                replaced
+            }
+        };
+        (replaced != target).then_some(replaced)
+    }
+}
+
+impl<'a> Plugin for AliasPlugin<'a> {
+    fn apply(&self, resolver: &Resolver, info: Info, context: &mut Context) -> State {
+        let inner_target = info.request().target();
+        let hits = self
+            .alias()
+            .iter()
+            .filter_map(|(from, array)| Hit::new(from, inner_target).map(|hit| (from, array, hit)))
+            .collect::<Vec<_>>();
+        // the pattern keys are ranked against each other in the slots they are
+        // declared, the stable sort keeps the order of the ties.
+        let mut order = (0..hits.len()).collect::<Vec<_>>();
+        let slots = order
+            .iter()
+            .copied()
+            .filter(|&index| hits[index].2.precedence(hits[index].0).is_some())
+            .collect::<Vec<_>>();
+        let mut ranked = slots.clone();
+        ranked.sort_by_key(|&index| Reverse(hits[index].2.precedence(hits[index].0)));
+        for (slot, index) in slots.into_iter().zip(ranked) {
+            order[slot] = index;
+        }
+        for &(from, array, ref hit) in order.into_iter().map(|index| &hits[index]) {
+            let key = from as *const AliasKey as usize;
+            if context.alias_keys.last() == Some(&key) {
+                // the request is rewritten by this key.
+                continue;
+            }
+            let in_cycle = context.alias_keys.contains(&key);
+            tracing::debug!(
+                "AliasPlugin works, triggered by '{from}'({})",
+                depth(&context.depth)
+            );
+            context.alias_keys.push(key);
+            let state = Self::resolve_hit(resolver, &info, hit, array, in_cycle, context);
+            context.alias_keys.pop();
+            if state.is_finished() {
+                return state;
             }
+            tracing::debug!("Leaving AliasPlugin({})", depth(&context.depth));
         }
 
         State::Resolving(info)
@@ -0,0 +1,165 @@
+use crate::{
+    log::color, log::depth, options::CheckLevel, Context, Diagnostic, Error, Info, ResolveResult,
+    Resolver, State,
+};
+use std::{
+    ffi::OsString,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+pub struct CaseMismatchPlugin;
+
+impl CaseMismatchPlugin {
+    pub fn apply(resolver: &Resolver, info: Info, context: &mut Context) -> State {
+        Self::apply_with(resolver, info, context, |dir| {
+            resolver.load_entry(dir).children()
+        })
+    }
+
+    /// Same as `apply`, but the names in a directory are listed by `read_dir`.
+    fn apply_with(
+        resolver: &Resolver,
+        info: Info,
+        context: &mut Context,
+        read_dir: impl Fn(&Path) -> Option<Arc<[OsString]>>,
+    ) -> State {
+        debug_assert!(info.request().target().is_empty());
+
+        if resolver.options.case_mismatch == CheckLevel::Ignore {
+            return State::Success(ResolveResult::Resource(info));
+        }
+
+        let path = info.normalized_path().as_ref();
+        let Some(actual) = find_case_mismatch(path, read_dir) else {
+            return State::Success(ResolveResult::Resource(info));
+        };
+        tracing::debug!(
+            "'{}' mismatches the case of '{}'({})",
+            color::blue(&path.display()),
+            color::blue(&actual.display()),
+            depth(&context.depth)
+        );
+        let message = format!(
+            "'{}' mismatches the case of the actual path '{}'",
+            path.display(),
+            actual.display()
+        );
+        match resolver.options.case_mismatch {
+            CheckLevel::Fail => State::Error(Error::CaseMismatch((path.into(), message))),
+            _ => {
+                context.report(Diagnostic::warning(message, path.to_path_buf()));
+                State::Success(ResolveResult::Resource(info))
+            }
+        }
+    }
+}
+
+/// Compare each component of `path` with the names listed in its parent
+/// directory, returns the actual path once a component is only matched
+/// by ignoring the case.
+fn find_case_mismatch(
+    path: &Path,
+    read_dir: impl Fn(&Path) -> Option<Arc<[OsString]>>,
+) -> Option<PathBuf> {
+    let mut dir = PathBuf::new();
+    let mut actual = PathBuf::new();
+    let mut mismatched = false;
+    for component in path.components() {
+        let Component::Normal(name) = component else {
+            dir.push(component);
+            actual.push(component);
+            continue;
+        };
+        let names = read_dir(&dir)?;
+        if names.iter().any(|listed| listed == name) {
+            actual.push(name);
+        } else {
+            let lowercase = name.to_string_lossy().to_lowercase();
+            let listed = names
+                .iter()
+                .find(|listed| listed.to_string_lossy().to_lowercase() == lowercase)?;
+            actual.push(listed);
+            mismatched = true;
+        }
+        dir.push(name);
+    }
+    mismatched.then_some(actual)
+}
+
+#[cfg(test)]
+fn case_insensitive_fs(path: &Path) -> Option<Arc<[OsString]>> {
+    let listed: &[(&str, &[&str])] = &[
+        ("/", &["src"]),
+        ("/src", &["button.tsx", "Components"]),
+        ("/src/Components", &["index.js"]),
+    ];
+    listed
+        .iter()
+        .find(|(dir, _)| dir.to_lowercase() == path.to_string_lossy().to_lowercase())
+        .map(|(_, names)| names.iter().map(OsString::from).collect())
+}
+
+#[test]
+fn find_case_mismatch_test() {
+    let read_dir = case_insensitive_fs;
+    assert_eq!(
+        find_case_mismatch(Path::new("/src/button.tsx"), read_dir),
+        None
+    );
+    assert_eq!(
+        find_case_mismatch(Path::new("/src/Button.tsx"), read_dir),
+        Some(PathBuf::from("/src/button.tsx"))
+    );
+    assert_eq!(
+        find_case_mismatch(Path::new("/SRC/components/index.js"), read_dir),
+        Some(PathBuf::from("/src/Components/index.js"))
+    );
+    assert_eq!(
+        find_case_mismatch(Path::new("/src/none.js"), read_dir),
+        None
+    );
+}
+
+#[test]
+fn case_mismatch_plugin_test() {
+    use crate::{info::NormalizedPath, Options, Severity};
+
+    let apply = |case_mismatch: CheckLevel, path: &str| {
+        let resolver = Resolver::new(Options {
+            case_mismatch,
+            ..Default::default()
+        });
+        let mut context = Context::new(false, false);
+        let info = Info::from(NormalizedPath::new(path));
+        let state =
+            CaseMismatchPlugin::apply_with(&resolver, info, &mut context, case_insensitive_fs);
+        (state, context.diagnostics)
+    };
+
+    let (state, diagnostics) = apply(CheckLevel::Report, "/src/Button.tsx");
+    assert!(matches!(state, State::Success(ResolveResult::Resource(_))));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(
+        diagnostics[0].message,
+        "'/src/Button.tsx' mismatches the case of the actual path '/src/button.tsx'"
+    );
+
+    let (state, diagnostics) = apply(CheckLevel::Fail, "/SRC/components/index.js");
+    match state {
+        State::Error(Error::CaseMismatch((location, message))) => {
+            assert_eq!(&*location, Path::new("/SRC/components/index.js"));
+            assert_eq!(
+                message,
+                "'/SRC/components/index.js' mismatches the case of the actual path '/src/Components/index.js'"
+            );
+        }
+        _ => unreachable!(),
+    }
+    assert!(diagnostics.is_empty());
+
+    let (state, diagnostics) = apply(CheckLevel::Report, "/src/button.tsx");
+    assert!(matches!(state, State::Success(ResolveResult::Resource(_))));
+    assert!(diagnostics.is_empty());
+}
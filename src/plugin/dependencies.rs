@@ -0,0 +1,184 @@
+use super::Plugin;
+use crate::{
+    description::DescriptionData,
+    log::color,
+    log::depth,
+    options::CheckLevel,
+    resolve::get_module_name_from_request,
+    semver::{Range, Version},
+    Context, Diagnostic, Error, Info, PathKind, Request, ResolveResult, Resolver, State,
+};
+use std::path::{Path, PathBuf};
+
+const DEPENDENCIES_FIELDS: [&str; 4] = [
+    "dependencies",
+    "peerDependencies",
+    "optionalDependencies",
+    "devDependencies",
+];
+
+/// Check the package resolved from `node_modules` with the dependencies
+/// declared in the description file of `importer`.
+pub struct DependenciesPlugin<'a> {
+    importer: &'a Path,
+    request: &'a Request,
+}
+
+impl<'a> DependenciesPlugin<'a> {
+    pub fn new(importer: &'a Path, request: &'a Request) -> Self {
+        Self { importer, request }
+    }
+
+    /// Returns the message once the installed version of package located
+    /// in `module_dir` doesn't satisfy the `declared` range.
+    fn check_version(
+        resolver: &Resolver,
+        module_name: &str,
+        module_dir: &Path,
+        declared: &str,
+        importer_pkg: &DescriptionData,
+        context: &mut Context,
+    ) -> Option<(String, PathBuf)> {
+        let range = parse_declared_range(declared)?;
+        let pkg_info = resolver
+            .load_entry(module_dir)
+            .pkg_info(resolver, context)
+            .ok()??;
+        if pkg_info.dir().as_ref() != module_dir {
+            return None;
+        }
+        let installed = pkg_info.data().raw().get("version")?.as_str()?;
+        if range.satisfies(&Version::parse(installed)?) {
+            return None;
+        }
+        let message = format!(
+            "'{module_name}@{installed}' located at '{}' doesn't satisfy '{declared}' declared in the dependencies of {}",
+            module_dir.display(),
+            importer_pkg.path().display()
+        );
+        Some((message, pkg_info.path().to_path_buf()))
+    }
+}
+
+impl<'a> Plugin for DependenciesPlugin<'a> {
+    fn apply(&self, resolver: &Resolver, info: Info, context: &mut Context) -> State {
+        let options = &resolver.options;
+        if (options.phantom_dependencies == CheckLevel::Ignore
+            && options.version_mismatch == CheckLevel::Ignore)
+            || !matches!(self.request.kind(), PathKind::Normal)
+        {
+            return State::Success(ResolveResult::Resource(info));
+        }
+        let module_name = get_module_name_from_request(self.request.target());
+        let resolved = info.normalized_path().as_ref();
+        let Some(module_dir) = resolver
+            .lookup_paths(self.importer)
+            .iter()
+            .map(|modules| modules.join(module_name))
+            .find(|module_dir| resolved.starts_with(module_dir))
+        else {
+            return State::Success(ResolveResult::Resource(info));
+        };
+        let pkg_info = match resolver
+            .load_entry(self.importer)
+            .pkg_info(resolver, context)
+        {
+            Ok(Some(pkg_info)) => pkg_info,
+            Ok(None) => return State::Success(ResolveResult::Resource(info)),
+            Err(err) => return State::Error(err),
+        };
+        if pkg_info.data().name() == Some(module_name) {
+            return State::Success(ResolveResult::Resource(info));
+        }
+
+        // the level of the check, the problem found by it and the error
+        // returned with `CheckLevel::Fail`.
+        type Fail = fn((Box<Path>, String)) -> Error;
+        let (level, problem, fail): (_, _, Fail) = match declared(&pkg_info, module_name) {
+            None => {
+                tracing::debug!(
+                    "'{}' is not declared in '{}'({})",
+                    color::blue(&module_name),
+                    color::blue(&pkg_info.path().display()),
+                    depth(&context.depth)
+                );
+                let message = format!(
+                    "'{module_name}' is resolved to '{}', but it is not declared in the dependencies of {}",
+                    resolved.display(),
+                    pkg_info.path().display()
+                );
+                (
+                    options.phantom_dependencies,
+                    Some((message, pkg_info.path().to_path_buf())),
+                    Error::PhantomDependency,
+                )
+            }
+            Some(declared) => match declared.as_str() {
+                Some(declared) if options.version_mismatch != CheckLevel::Ignore => (
+                    options.version_mismatch,
+                    Self::check_version(
+                        resolver,
+                        module_name,
+                        &module_dir,
+                        declared,
+                        &pkg_info,
+                        context,
+                    ),
+                    Error::VersionMismatch,
+                ),
+                _ => (CheckLevel::Ignore, None, Error::PhantomDependency),
+            },
+        };
+        match (level, problem) {
+            (CheckLevel::Ignore, _) | (_, None) => State::Success(ResolveResult::Resource(info)),
+            (CheckLevel::Fail, Some((message, location))) => {
+                State::Error(fail((location.into(), message)))
+            }
+            (CheckLevel::Report, Some((message, location))) => {
+                context.report(Diagnostic::warning(message, location));
+                State::Success(ResolveResult::Resource(info))
+            }
+        }
+    }
+}
+
+/// Returns the value of `module_name` declared in the dependencies of
+/// the description file.
+fn declared<'a>(pkg_info: &'a DescriptionData, module_name: &str) -> Option<&'a serde_json::Value> {
+    DEPENDENCIES_FIELDS.iter().find_map(|field| {
+        pkg_info
+            .data()
+            .raw()
+            .get(field)?
+            .as_object()?
+            .get(module_name)
+    })
+}
+
+/// Parse the declared range, the protocols except `workspace:` and `npm:`,
+/// such as `file:` and git urls, are not comparable and return `None`.
+fn parse_declared_range(declared: &str) -> Option<Range> {
+    let declared = declared.strip_prefix("workspace:").unwrap_or(declared);
+    let declared = match declared.strip_prefix("npm:") {
+        // `npm:name@range`
+        Some(aliased) => aliased.rsplit_once('@').map_or("", |(_, range)| range),
+        None => declared,
+    };
+    Range::parse(declared)
+}
+
+#[test]
+fn parse_declared_range_test() {
+    let satisfies = |declared: &str, version: &str| {
+        parse_declared_range(declared)
+            .map(|range| range.satisfies(&Version::parse(version).unwrap()))
+    };
+    assert_eq!(satisfies("^1.2.0", "1.3.0"), Some(true));
+    assert_eq!(satisfies("^1.2.0", "2.0.0"), Some(false));
+    assert_eq!(satisfies("workspace:~1.2.0", "1.3.0"), Some(false));
+    assert_eq!(satisfies("workspace:*", "1.3.0"), Some(true));
+    assert_eq!(satisfies("npm:@scope/foo@^2", "2.1.0"), Some(true));
+    assert_eq!(satisfies("npm:foo@^2", "1.0.0"), Some(false));
+    assert_eq!(satisfies("file:../foo", "1.0.0"), None);
+    assert_eq!(satisfies("latest", "1.0.0"), None);
+}
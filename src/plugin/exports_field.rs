@@ -4,7 +4,7 @@ use crate::{
     log::depth,
     map::{ExportsField, Field},
     resolve::get_path_from_request,
-    Context, Error, Info, Resolver, State,
+    Context, Diagnostic, Error, Info, Resolver, State,
 };
 
 use super::Plugin;
@@ -70,43 +70,54 @@ impl<'a> Plugin for ExportsFieldPlugin<'a> {
                 normalized_target
             };
 
-            let list = match ExportsField::field_process(
+            let mapping = match ExportsField::field_process(
                 root,
                 &remaining_target,
-                &resolver.options.condition_names,
+                resolver.condition_names(context),
             ) {
-                Ok(list) => list,
+                Ok(mapping) => mapping,
                 Err(err) => return State::Error(err),
             };
 
-            if list.is_empty() {
+            if mapping.targets.is_empty() {
                 return State::Error(Error::UnexpectedValue(format!(
-                    "Package path {target} is not exported in {}/package.json",
-                    self.pkg_info.dir().as_ref().display()
+                    "Package path {target} is not exported in {}",
+                    self.pkg_info.path().display()
                 )));
             }
 
-            for item in list {
+            if let Some(folder_key) = mapping.folder_key {
+                let key_path = field.iter().cloned().chain([folder_key]).collect();
+                context.report(
+                    Diagnostic::warning(
+                        format!(
+                            "Use of deprecated folder mapping for '{target}' in the \"{}\" field, use subpath patterns instead",
+                            field.join(".")
+                        ),
+                        self.pkg_info.path().to_path_buf(),
+                    )
+                    .with_key_path(key_path),
+                );
+            }
+
+            for item in mapping.targets {
                 tracing::debug!(
                     "ExportsField in '{}' works, trigger by '{}', mapped to '{}'({})",
-                    color::blue(&format!(
-                        "{}/package.json",
-                        self.pkg_info.dir().as_ref().display()
-                    )),
+                    color::blue(&self.pkg_info.path().display()),
                     color::blue(&target),
                     color::blue(&item),
                     depth(&context.depth)
                 );
                 if !item.starts_with("./") {
                     return State::Error(Error::UnexpectedValue(format!(
-                        "Invalid \"{item}\" defined in {}/package.json, target must start with  \"./\"",
-                        self.pkg_info.dir().as_ref().display()
+                        "Invalid \"{item}\" defined in {}, target must start with  \"./\"",
+                        self.pkg_info.path().display()
                     )));
                 }
                 let request = Resolver::parse(&item);
                 let info = Info::from(self.pkg_info.dir().clone()).with_request(request);
                 if let Err(msg) = ExportsField::check_target(info.request().target()) {
-                    let msg = format!("{msg} in {:?}/package.json", &self.pkg_info.dir());
+                    let msg = format!("{msg} in {}", self.pkg_info.path().display());
                     return State::Error(Error::UnexpectedValue(msg));
                 }
                 let state = resolver._resolve(info, context);
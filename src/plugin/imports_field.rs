@@ -21,7 +21,7 @@ impl<'a> ImportsFieldPlugin<'a> {
         let path = info.to_resolved_path();
         if resolver.load_entry(&path).is_file() {
             if let Err(msg) = ImportsField::check_target(info.request().target()) {
-                let msg = format!("{msg} in {:?}/package.json", &self.pkg_info.dir().as_ref());
+                let msg = format!("{msg} in {}", self.pkg_info.path().display());
                 State::Error(Error::UnexpectedValue(msg))
             } else {
                 State::Resolving(info)
@@ -42,7 +42,12 @@ impl<'a> Plugin for ImportsFieldPlugin<'a> {
             return State::Resolving(info);
         }
 
-        let root = match self.pkg_info.data().raw().get("imports") {
+        let root = match resolver
+            .options
+            .imports_field
+            .iter()
+            .find_map(|field| self.pkg_info.data().get_filed(field))
+        {
             Some(tree) => tree,
             None => return State::Resolving(info),
         };
@@ -50,16 +55,16 @@ impl<'a> Plugin for ImportsFieldPlugin<'a> {
         let list = match ImportsField::field_process(
             root,
             info.request().target(),
-            &resolver.options.condition_names,
+            resolver.condition_names(context),
         ) {
-            Ok(list) => list,
+            Ok(mapping) => mapping.targets,
             Err(err) => return State::Error(err),
         };
 
         if let Some(item) = list.first() {
             tracing::debug!(
                 "ImportsField in '{}' works, trigger by '{}', mapped to '{}'({})",
-                color::blue(&format!("{:?}/package.json", self.pkg_info.dir().as_ref())),
+                color::blue(&self.pkg_info.path().display()),
                 color::blue(&info.request().target()),
                 color::blue(&item),
                 depth(&context.depth)
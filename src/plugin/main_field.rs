@@ -1,5 +1,8 @@
 use super::Plugin;
-use crate::{description::DescriptionData, log::color, log::depth, Context, Info, Resolver, State};
+use crate::{
+    description::DescriptionData, log::color, log::depth, Context, Diagnostic, Info, Resolver,
+    State,
+};
 
 pub struct MainFieldPlugin<'a> {
     pkg_info: &'a DescriptionData,
@@ -20,12 +23,15 @@ impl<'a> Plugin for MainFieldPlugin<'a> {
         let main_field_info = info.clone().with_path(resolved).with_target(".");
 
         for user_main_field in &resolver.options.main_fields {
-            if let Some(main_field) = self
-                .pkg_info
-                .data()
-                .raw()
-                .get(user_main_field)
-                .and_then(|value| value.as_str())
+            if let Some(main_field) =
+                self.pkg_info
+                    .data()
+                    .get_filed(user_main_field)
+                    .and_then(|value| match value {
+                        // the object form of `browser`.
+                        serde_json::Value::Object(map) => map.get(".")?.as_str(),
+                        _ => value.as_str(),
+                    })
             {
                 if main_field == "." || main_field == "./" {
                     // if it pointed to itself.
@@ -33,8 +39,8 @@ impl<'a> Plugin for MainFieldPlugin<'a> {
                 }
                 tracing::debug!(
                     "MainField in '{}' works, using {} field({})",
-                    color::blue(&format!("{:?}/package.json", self.pkg_info.dir().as_ref())),
-                    color::blue(user_main_field),
+                    color::blue(&self.pkg_info.path().display()),
+                    color::blue(&user_main_field.join(".")),
                     depth(&context.depth)
                 );
 
@@ -57,6 +63,16 @@ impl<'a> Plugin for MainFieldPlugin<'a> {
                 if state.is_finished() {
                     return state;
                 }
+                context.report(
+                    Diagnostic::warning(
+                        format!(
+                            "'{main_field}' in the \"{}\" field can't be resolved, fallback to the next field or the main files",
+                            user_main_field.join(".")
+                        ),
+                        self.pkg_info.path().to_path_buf(),
+                    )
+                    .with_key_path(user_main_field.clone()),
+                );
                 tracing::debug!("Leaving MainField({})", depth(&context.depth));
             }
         }
@@ -6,7 +6,7 @@ pub struct MainFilePlugin;
 impl Plugin for MainFilePlugin {
     fn apply(&self, resolver: &Resolver, info: Info, context: &mut Context) -> State {
         let path = info.to_resolved_path();
-        for main_file in &resolver.options.main_files {
+        for (index, main_file) in resolver.options.main_files.iter().enumerate() {
             tracing::debug!(
                 "MainFile works, it pointed to {}({})",
                 color::blue(main_file),
@@ -18,6 +18,17 @@ impl Plugin for MainFilePlugin {
                 .with_target(&format!("./{main_file}"));
             let state = resolver._resolve(main_file_info, context);
             if state.is_finished() {
+                if resolver.options.detect_ambiguity {
+                    for main_file in &resolver.options.main_files[index + 1..] {
+                        let main_file_info = info
+                            .clone()
+                            .with_path(&path)
+                            .with_target(&format!("./{main_file}"));
+                        resolver.probe_shadowed(&state, context, |resolver, context| {
+                            resolver._resolve(main_file_info, context)
+                        });
+                    }
+                }
                 return state;
             }
             tracing::debug!("Leaving MainFile({})", depth(&context.depth));
@@ -1,5 +1,7 @@
 mod alias;
-mod browser_field;
+mod alias_field;
+mod case_mismatch;
+mod dependencies;
 mod exports_field;
 mod extension_alias;
 mod imports_field;
@@ -7,12 +9,17 @@ mod main_field;
 mod main_file;
 mod parse;
 mod prefer_relative;
+mod roots;
+mod source_redirect;
 mod symlink;
+mod types_versions;
 
 use crate::{context::Context, Info, Resolver, State};
 
 pub use alias::AliasPlugin;
-pub use browser_field::BrowserFieldPlugin;
+pub use alias_field::AliasFieldPlugin;
+pub use case_mismatch::CaseMismatchPlugin;
+pub use dependencies::DependenciesPlugin;
 pub use exports_field::ExportsFieldPlugin;
 pub use extension_alias::ExtensionAliasPlugin;
 pub use imports_field::ImportsFieldPlugin;
@@ -20,8 +27,62 @@ pub use main_field::MainFieldPlugin;
 pub use main_file::MainFilePlugin;
 pub use parse::ParsePlugin;
 pub use prefer_relative::PreferRelativePlugin;
+pub use roots::RootsPlugin;
+pub use source_redirect::SourceRedirectPlugin;
 pub use symlink::SymlinkPlugin;
+pub use types_versions::TypesVersionsPlugin;
 
 pub(crate) trait Plugin {
     fn apply(&self, resolver: &Resolver, info: Info, context: &mut Context) -> State;
 }
+
+/// The points of the pipeline where the `ResolverPlugin`s are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookPoint {
+    /// Before the request is parsed, it is applied on every (including the
+    /// nested) resolving.
+    BeforeResolve,
+    /// After the `alias` and `prefer_relative` are applied.
+    AfterAlias,
+    /// After the `imports` and `browser` field of the description file which
+    /// the importer belongs to are applied.
+    DescribedResolve,
+    /// Before the bare specifier is looked up in `modules`.
+    BeforeModuleLookup,
+    /// The request is resolved, the `info` is the result.
+    /// Returning `State::Failed` rejects this result.
+    Result,
+    /// The request can't be resolved even though `fallback` is applied.
+    /// Returning `State::Success` recovers it.
+    OnFailed,
+}
+
+/// The plugin provided by `Options::plugins`.
+pub trait ResolverPlugin: std::fmt::Debug + Send + Sync {
+    /// It is applied at every `HookPoint` in the order of `Options::plugins`.
+    /// Returns `State::Resolving` with the (maybe changed) `info` to continue the
+    /// pipeline, and the other states to stop it.
+    fn apply(
+        &self,
+        hook: HookPoint,
+        resolver: &Resolver,
+        info: Info,
+        context: &mut Context,
+    ) -> State;
+}
+
+impl Resolver {
+    pub(crate) fn apply_plugins(
+        &self,
+        hook: HookPoint,
+        info: Info,
+        context: &mut Context,
+    ) -> State {
+        self.options
+            .plugins
+            .iter()
+            .fold(State::Resolving(info), |state, plugin| {
+                state.then(|info| plugin.apply(hook, self, info, context))
+            })
+    }
+}
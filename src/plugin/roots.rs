@@ -0,0 +1,48 @@
+use crate::{kind::PathKind, log::color, log::depth, Context, Info, Resolver, State};
+
+pub struct RootsPlugin;
+
+impl RootsPlugin {
+    pub fn apply(resolver: &Resolver, info: Info, context: &mut Context) -> State {
+        if !matches!(info.request().kind(), PathKind::AbsolutePosix)
+            || resolver.options.roots.is_empty()
+        {
+            return resolver.resolve_as_path(info, context);
+        }
+
+        if resolver.options.prefer_absolute {
+            Self::resolve_as_absolute(resolver, info, context)
+                .then(|info| Self::resolve_in_roots(resolver, info, context))
+        } else {
+            Self::resolve_in_roots(resolver, info, context)
+                .then(|info| Self::resolve_as_absolute(resolver, info, context))
+        }
+        .then(State::Failed)
+    }
+
+    fn resolve_as_absolute(resolver: &Resolver, info: Info, context: &mut Context) -> State {
+        match resolver.resolve_as_path(info, context) {
+            State::Failed(info) => State::Resolving(info),
+            state => state,
+        }
+    }
+
+    fn resolve_in_roots(resolver: &Resolver, info: Info, context: &mut Context) -> State {
+        let target = format!(".{}", info.request().target());
+        for root in &resolver.options.roots {
+            tracing::debug!(
+                "RootsPlugin works, try '{}' in '{}'({})",
+                color::blue(&info.request().target()),
+                color::blue(&root.display()),
+                depth(&context.depth)
+            );
+            let root_info = Info::new(root, info.request().clone()).with_target(&target);
+            let state = resolver._resolve(root_info, context);
+            if state.is_finished() {
+                return state;
+            }
+        }
+        tracing::debug!("Leaving RootsPlugin({})", depth(&context.depth));
+        State::Resolving(info)
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    entry::Entry, log::color, log::depth, tsconfig::CompilerOptions, Context, Info, ResolveResult,
+    Resolver, State,
+};
+use path_absolutize::Absolutize;
+use std::path::{Path, PathBuf};
+
+/// Maps the output extension to the extensions of its TypeScript source.
+const SOURCE_EXTENSIONS: [(&str, &[&str]); 7] = [
+    (".d.ts", &[".ts", ".tsx"]),
+    (".d.mts", &[".mts"]),
+    (".d.cts", &[".cts"]),
+    (".js", &[".ts", ".tsx"]),
+    (".jsx", &[".tsx"]),
+    (".mjs", &[".mts"]),
+    (".cjs", &[".cts"]),
+];
+
+pub struct SourceRedirectPlugin;
+
+impl SourceRedirectPlugin {
+    pub fn apply(resolver: &Resolver, info: Info, context: &mut Context) -> State {
+        debug_assert!(info.request().target().is_empty());
+
+        if !resolver.options.redirect_to_source {
+            return State::Success(ResolveResult::Resource(info));
+        }
+
+        let path = info.normalized_path().as_ref();
+        let pkg_info = match resolver.load_entry(path).pkg_info(resolver, context) {
+            Ok(Some(pkg_info)) => pkg_info,
+            Ok(None) => return State::Success(ResolveResult::Resource(info)),
+            Err(err) => return State::Error(err),
+        };
+        let pkg_dir = pkg_info.dir().as_ref();
+        let Some(real_pkg_dir) = linked_package_dir(resolver, pkg_dir) else {
+            return State::Success(ResolveResult::Resource(info));
+        };
+        // the tsconfig is read from the real location, so that the relative
+        // `extends` works as the `tsc` in the linked package does.
+        let path = &real_pkg_dir.join(path.strip_prefix(pkg_dir).unwrap_or(path));
+
+        let tsconfig_path = real_pkg_dir.join("tsconfig.json");
+        if !resolver.load_entry(&tsconfig_path).is_file() {
+            return State::Success(ResolveResult::Resource(info));
+        }
+        let compiler_options = match resolver.parse_ts_file(&tsconfig_path, context) {
+            Ok(tsconfig) => tsconfig.compiler_options,
+            Err(err) => return State::Error(err),
+        };
+        let Some(compiler_options) = compiler_options else {
+            return State::Success(ResolveResult::Resource(info));
+        };
+
+        match find_source(resolver, &real_pkg_dir, &compiler_options, path) {
+            Some(source) => {
+                tracing::debug!(
+                    "SourceRedirect works, '{}' is redirected to '{}'({})",
+                    color::blue(&path.display()),
+                    color::blue(&source.display()),
+                    depth(&context.depth)
+                );
+                State::Success(ResolveResult::Resource(info.with_path(source)))
+            }
+            None => State::Success(ResolveResult::Resource(info)),
+        }
+    }
+}
+
+/// Returns the real path of `pkg_dir` if it is reached through a symlink
+/// inside `node_modules`, such as the linked workspace packages. The
+/// symlinks above the `node_modules` are not counted, otherwise every
+/// package is redirected once the project itself is under a symlink.
+fn linked_package_dir(resolver: &Resolver, pkg_dir: &Path) -> Option<PathBuf> {
+    let is_modules = |entry: &Entry| {
+        entry.path().file_name().map_or(false, |name| {
+            resolver
+                .options
+                .modules
+                .iter()
+                .any(|module| Path::new(module).is_relative() && name == module.as_str())
+        })
+    };
+    let mut entry = resolver.load_entry(pkg_dir);
+    let mut linked = None;
+    loop {
+        let parent = entry.parent()?.clone();
+        if linked.is_none() {
+            linked = entry
+                .symlink()
+                .as_ref()
+                .map(|real| (real.clone(), entry.clone()));
+        }
+        if is_modules(&parent) {
+            let (real, entry) = linked?;
+            let tail = pkg_dir.strip_prefix(entry.path()).ok()?;
+            return Some(real.join(tail));
+        }
+        entry = parent;
+    }
+}
+
+fn find_source(
+    resolver: &Resolver,
+    pkg_dir: &Path,
+    compiler_options: &CompilerOptions,
+    path: &Path,
+) -> Option<PathBuf> {
+    // `${configDir}` is the directory of the tsconfig used by the package.
+    let resolve_dir = |dir: &str| {
+        let dir = match dir.strip_prefix("${configDir}") {
+            Some(relative) => pkg_dir.join(relative.trim_start_matches(['/', '\\'])),
+            None => pkg_dir.join(dir),
+        };
+        dir.absolutize_from(Path::new("")).ok().map(PathBuf::from)
+    };
+    let root_dir = resolve_dir(compiler_options.root_dir.as_deref().unwrap_or("."))?;
+    let output_dirs = [
+        compiler_options.declaration_dir.as_deref(),
+        compiler_options.out_dir.as_deref(),
+    ];
+    for output_dir in output_dirs.into_iter().flatten().filter_map(resolve_dir) {
+        let Ok(relative) = path.strip_prefix(output_dir) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy();
+        for (output_ext, source_exts) in SOURCE_EXTENSIONS {
+            let Some(stem) = relative.strip_suffix(output_ext) else {
+                continue;
+            };
+            for source_ext in source_exts {
+                let source = root_dir.join(format!("{stem}{source_ext}"));
+                if resolver.load_entry(&source).is_file() {
+                    return Some(source);
+                }
+            }
+            break;
+        }
+    }
+    None
+}
@@ -0,0 +1,105 @@
+use super::Plugin;
+use crate::{
+    description::DescriptionData,
+    log::color,
+    log::depth,
+    resolve::get_path_from_request,
+    semver::{Range, Version},
+    Context, Info, Resolver, State,
+};
+
+pub struct TypesVersionsPlugin<'a> {
+    pkg_info: &'a DescriptionData,
+}
+
+impl<'a> TypesVersionsPlugin<'a> {
+    pub fn new(pkg_info: &'a DescriptionData) -> Self {
+        Self { pkg_info }
+    }
+
+    /// Returns the path mappings of the first entry in `typesVersions`
+    /// whose range is satisfied by `typescript_version`.
+    fn paths(
+        &self,
+        typescript_version: &str,
+    ) -> Option<&'a serde_json::Map<String, serde_json::Value>> {
+        let version = Version::parse(typescript_version)?;
+        self.pkg_info
+            .data()
+            .raw()
+            .get("typesVersions")?
+            .as_object()?
+            .iter()
+            .find(|(range, _)| Range::parse(range).map_or(false, |range| range.satisfies(&version)))
+            .and_then(|(_, paths)| paths.as_object())
+    }
+
+    /// The path of declaration file, relative to package, which
+    /// the request pointed to.
+    fn subpath(&self, info: &Info) -> String {
+        match get_path_from_request(info.request().target()) {
+            Some(path) => path.trim_start_matches('/').to_string(),
+            None => ["types", "typings"]
+                .iter()
+                .find_map(|field| self.pkg_info.data().raw().get(field)?.as_str())
+                .unwrap_or("index.d.ts")
+                .trim_start_matches("./")
+                .to_string(),
+        }
+    }
+}
+
+impl<'a> Plugin for TypesVersionsPlugin<'a> {
+    fn apply(&self, resolver: &Resolver, info: Info, context: &mut Context) -> State {
+        if !resolver.options.types {
+            return State::Resolving(info);
+        }
+        let Some(paths) = self.paths(&resolver.options.typescript_version) else {
+            return State::Resolving(info);
+        };
+        let subpath = self.subpath(&info);
+
+        // exact match first, and then the pattern with the longest prefix.
+        let matched = paths
+            .get_key_value(&subpath)
+            .map(|(key, targets)| (key, targets, ""))
+            .or_else(|| {
+                paths
+                    .iter()
+                    .filter_map(|(pattern, targets)| {
+                        Resolver::match_star(pattern, &subpath).map(|star| (pattern, targets, star))
+                    })
+                    .max_by_key(|(pattern, _, _)| pattern.find('*'))
+            });
+        let Some((pattern, targets, star)) = matched else {
+            return State::Resolving(info);
+        };
+
+        tracing::debug!(
+            "TypesVersions in '{}' works, trigger by '{}'({})",
+            color::blue(&self.pkg_info.path().display()),
+            color::blue(pattern),
+            depth(&context.depth)
+        );
+        for target in targets.as_array().into_iter().flatten() {
+            let Some(target) = target.as_str() else {
+                continue;
+            };
+            let target = target.replace('*', star);
+            let target = if target.starts_with("./") {
+                target
+            } else {
+                format!("./{target}")
+            };
+            let types_info = Info::from(self.pkg_info.dir().clone())
+                .with_request(info.request().clone())
+                .with_target(&target);
+            let state = resolver._resolve(types_info, context);
+            if state.is_finished() {
+                return state;
+            }
+        }
+        tracing::debug!("Leaving TypesVersions({})", depth(&context.depth));
+        State::Resolving(info)
+    }
+}
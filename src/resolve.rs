@@ -3,11 +3,12 @@ use crate::{
     info::NormalizedPath,
     kind::PathKind,
     log::color,
+    options::DECLARATION_EXTENSIONS,
     plugin::{
-        BrowserFieldPlugin, ExportsFieldPlugin, ExtensionAliasPlugin, ImportsFieldPlugin,
-        MainFieldPlugin, MainFilePlugin, Plugin,
+        AliasFieldPlugin, ExportsFieldPlugin, ExtensionAliasPlugin, ImportsFieldPlugin,
+        MainFieldPlugin, MainFilePlugin, Plugin, SymlinkPlugin, TypesVersionsPlugin,
     },
-    Context, EnforceExtension, Info, ResolveResult, Resolver, State,
+    Context, Diagnostic, EnforceExtension, Info, ResolveResult, Resolver, Restriction, State,
 };
 use std::{
     borrow::Cow,
@@ -15,19 +16,41 @@ use std::{
 };
 
 impl Resolver {
-    fn resolve_file_with_ext(&self, mut path: PathBuf, info: Info) -> State {
+    fn resolve_file_with_ext(&self, mut path: PathBuf, info: Info, context: &mut Context) -> State {
+        let tsconfig = context.tsconfig.clone();
+        let tsconfig = tsconfig.as_deref();
+        let no_suffix = [String::new()];
+        let module_suffixes = tsconfig.map_or(&no_suffix[..], |tsconfig| &tsconfig.module_suffixes);
+        let mut resolved: Option<PathBuf> = None;
         let v = unsafe { &mut *(&mut path as *mut PathBuf as *mut Vec<u8>) };
         for ext in &self.options.extensions {
-            v.extend_from_slice(ext.as_bytes());
-            if self.load_entry(path.as_ref()).is_file() {
-                return State::Success(ResolveResult::Resource(
-                    info.with_path(path).with_target(""),
-                ));
+            if ext == ".json" && tsconfig.map_or(false, |tsconfig| !tsconfig.is_allowed_json()) {
+                continue;
             }
-            unsafe {
-                v.set_len(v.len() - ext.len());
+            for suffix in module_suffixes {
+                v.extend_from_slice(suffix.as_bytes());
+                v.extend_from_slice(ext.as_bytes());
+                if self.load_entry(path.as_ref()).is_file() {
+                    match &resolved {
+                        None if !self.options.detect_ambiguity => {
+                            return State::Success(ResolveResult::Resource(
+                                info.with_path(path).with_target(""),
+                            ));
+                        }
+                        None => resolved = Some(path.clone()),
+                        Some(winner) => context.report(shadowed_diagnostic(&path, winner)),
+                    }
+                }
+                unsafe {
+                    v.set_len(v.len() - suffix.len() - ext.len());
+                }
             }
         }
+        if let Some(path) = resolved {
+            return State::Success(ResolveResult::Resource(
+                info.with_path(path).with_target(""),
+            ));
+        }
         tracing::debug!(
             "'{}[{}]' is not a file",
             color::red(&path.display()),
@@ -60,8 +83,7 @@ impl Resolver {
         let path = info.to_resolved_path();
         let request = info.request();
         let target = request.target();
-        if self.load_entry(&path).is_file() {
-            let path = path.to_path_buf();
+        if let Some(path) = self.probe_file_with_suffixes(&path, context) {
             State::Success(ResolveResult::Resource(
                 info.with_path(path).with_target(""),
             ))
@@ -70,7 +92,14 @@ impl Resolver {
             PathKind::AbsolutePosix | PathKind::AbsoluteWin | PathKind::Relative
         ) || split_slash_from_request(target).is_some()
         {
-            State::Failed(info)
+            if self.options.types {
+                // TypeScript maps `./foo.js` to `./foo.ts` in the fully
+                // specified requests too.
+                self.resolve_extension_alias(info, context)
+                    .then(State::Failed)
+            } else {
+                State::Failed(info)
+            }
         } else {
             let dir = path.to_path_buf();
             let info = info.with_path(dir).with_target(".");
@@ -90,30 +119,54 @@ impl Resolver {
             return State::Resolving(info);
         }
 
-        self.options
-            .extension_alias
-            .iter()
-            .fold(State::Resolving(info), |state, (extension, alias_list)| {
+        self.resolve_extension_alias(info, context).then(|info| {
+            let path = info.to_resolved_path().to_path_buf();
+            tracing::debug!(
+                "Attempting to load '{}' as a file",
+                color::blue(&path.display())
+            );
+            if matches!(self.options.enforce_extension, EnforceExtension::Enabled) {
+                self.resolve_file_with_ext(path, info, context)
+            } else if let Some(path) = self.probe_file_with_suffixes(&path, context) {
+                State::Success(ResolveResult::Resource(
+                    info.with_path(path).with_target(""),
+                ))
+            } else {
+                self.resolve_file_with_ext(path, info, context)
+            }
+        })
+    }
+
+    /// Probes `path` with each `moduleSuffixes` of tsconfig inserted before
+    /// its extension, such as `foo.ios.js` for `foo.js`.
+    fn probe_file_with_suffixes(&self, path: &Path, context: &Context) -> Option<PathBuf> {
+        let module_suffixes = context
+            .tsconfig
+            .as_ref()
+            .map_or(&[][..], |tsconfig| &tsconfig.module_suffixes[..]);
+        let name = path.file_name().and_then(|name| name.to_str());
+        let extension = name.and_then(file_extension);
+        match (name, extension) {
+            (Some(name), Some(extension)) if !module_suffixes.is_empty() => {
+                let stem = &name[..name.len() - extension.len()];
+                module_suffixes
+                    .iter()
+                    .map(|suffix| path.with_file_name(format!("{stem}{suffix}{extension}")))
+                    .find(|path| self.load_entry(path).is_file())
+            }
+            _ => self.load_entry(path).is_file().then(|| path.to_path_buf()),
+        }
+    }
+
+    fn resolve_extension_alias(&self, info: Info, context: &mut Context) -> State {
+        self.options.extension_alias.iter().fold(
+            State::Resolving(info),
+            |state, (extension, alias_list)| {
                 state.then(|info| {
                     ExtensionAliasPlugin::new(extension, alias_list).apply(self, info, context)
                 })
-            })
-            .then(|info| {
-                let path = info.to_resolved_path().to_path_buf();
-                tracing::debug!(
-                    "Attempting to load '{}' as a file",
-                    color::blue(&path.display())
-                );
-                if matches!(self.options.enforce_extension, EnforceExtension::Enabled) {
-                    self.resolve_file_with_ext(path, info)
-                } else if self.load_entry(&path).is_file() {
-                    State::Success(ResolveResult::Resource(
-                        info.with_path(path).with_target(""),
-                    ))
-                } else {
-                    self.resolve_file_with_ext(path, info)
-                }
-            })
+            },
+        )
     }
 
     pub(crate) fn resolve_as_dir(&self, info: Info, context: &mut Context) -> State {
@@ -122,19 +175,55 @@ impl Resolver {
         if !entry.is_dir() {
             return State::Failed(info);
         }
-        let pkg_info = match entry.pkg_info(self) {
+        let pkg_info = match entry.pkg_info(self, context) {
             Ok(pkg_info) => pkg_info,
             Err(err) => return State::Error(err),
         };
         if let Some(pkg_info) = pkg_info {
-            MainFieldPlugin::new(pkg_info).apply(self, info, context)
+            MainFieldPlugin::new(&pkg_info).apply(self, info, context)
         } else {
             State::Resolving(info)
         }
         .then(|info| MainFilePlugin.apply(self, info, context))
     }
 
+    pub(crate) fn resolve_as_path(&self, info: Info, context: &mut Context) -> State {
+        self.resolve_as_context(info, context)
+            .then(|info| self.resolve_as_fully_specified(info, context))
+            .then(|info| self.resolve_as_file(info, context))
+            .then(|info| self.resolve_as_dir(info, context))
+    }
+
+    /// Checks the restrictions against the path returned to the caller,
+    /// which is the real path of `info` if `symlinks` is enabled.
+    pub(crate) fn is_satisfied_restrictions(&self, info: &Info, context: &mut Context) -> bool {
+        if self.options.restrictions.is_empty() {
+            return true;
+        }
+        let real = match SymlinkPlugin::apply(self, info.clone(), context) {
+            State::Success(ResolveResult::Resource(real)) => real,
+            _ => info.clone(),
+        };
+        let path = real.normalized_path().as_ref();
+        self.options
+            .restrictions
+            .iter()
+            .all(|restriction| match restriction {
+                Restriction::Path(prefix) => path.starts_with(prefix),
+                Restriction::Regex(regex) => regex.is_match(&path.to_string_lossy()),
+            })
+    }
+
     pub(crate) fn resolve_as_modules(&self, info: Info, context: &mut Context) -> State {
+        let info = match self
+            .resolve_workspace_package(info, context)
+            .then(|info| self.resolve_manifest_package(info, context))
+        {
+            State::Resolving(info) => info,
+            state => return state,
+        };
+        let is_ancestor = context.lookup_ancestor.get();
+        context.lookup_ancestor.set(false);
         let original_dir = info.normalized_path();
         for module in &self.options.modules {
             let node_modules_path = Path::new(module);
@@ -143,24 +232,129 @@ impl Resolver {
             } else {
                 (Cow::Owned(original_dir.as_ref().join(module)), true)
             };
-            let state = self
-                ._resolve_as_modules(info.clone(), original_dir, &node_modules_path, context)
-                .then(|info| {
-                    if !need_find_up {
-                        State::Resolving(info)
-                    } else if let Some(parent_dir) = original_dir.as_ref().parent() {
-                        self._resolve(info.with_path(parent_dir), context)
-                    } else {
-                        State::Resolving(info)
-                    }
-                });
+            let state =
+                self._resolve_as_modules(info.clone(), original_dir, &node_modules_path, context);
+            self.report_shadowed_modules(&state, &info, &node_modules_path, context);
+            let state = state.then(|info| {
+                if !need_find_up {
+                    State::Resolving(info)
+                } else if let Some(parent_dir) = self.modules_parent_dir(original_dir.as_ref()) {
+                    context.lookup_ancestor.set(true);
+                    let state = self._resolve(info.with_path(parent_dir), context);
+                    context.lookup_ancestor.set(false);
+                    state
+                } else {
+                    State::Resolving(info)
+                }
+            });
             if state.is_finished() {
                 return state;
             }
         }
+        if !is_ancestor {
+            // the global folders are looked up after all the ancestors.
+            for global_dir in &self.global_dirs {
+                let state =
+                    self._resolve_as_modules(info.clone(), original_dir, global_dir, context);
+                if state.is_finished() {
+                    return state;
+                }
+            }
+        }
         State::Failed(info)
     }
 
+    /// Once `state` is resolved in `modules_dir`, report the nearest
+    /// different package with the same name in the other lookup paths,
+    /// the global folders are not included.
+    fn report_shadowed_modules(
+        &self,
+        state: &State,
+        info: &Info,
+        modules_dir: &Path,
+        context: &mut Context,
+    ) {
+        if !self.options.detect_ambiguity || !matches!(state, State::Success(_)) {
+            return;
+        }
+        let original_dir = info.normalized_path();
+        let module_name = get_module_name_from_request(info.request().target());
+        for lookup_path in self.lookup_paths(original_dir.as_ref()) {
+            if lookup_path == modules_dir
+                || self.global_dirs.contains(&lookup_path)
+                || !self.load_entry(&lookup_path.join(module_name)).exists()
+            {
+                continue;
+            }
+            let reported = self.probe_shadowed(state, context, |resolver, context| {
+                resolver._resolve_as_modules(info.clone(), original_dir, &lookup_path, context)
+            });
+            if reported {
+                break;
+            }
+        }
+    }
+
+    /// Run `probe` after `winner` is resolved, and report the resolved path
+    /// of `probe` if its real path is a different one, returns whether it
+    /// is reported. The diagnostics reported during `probe` are dropped
+    /// since its result is never used.
+    pub(crate) fn probe_shadowed(
+        &self,
+        winner: &State,
+        context: &mut Context,
+        probe: impl FnOnce(&Self, &mut Context) -> State,
+    ) -> bool {
+        let State::Success(ResolveResult::Resource(winner)) = winner else {
+            return false;
+        };
+        let len = context.diagnostics.len();
+        let state = probe(self, context);
+        context.diagnostics.truncate(len);
+        let State::Success(ResolveResult::Resource(shadowed)) = state else {
+            return false;
+        };
+        let shadowed = shadowed.normalized_path().as_ref();
+        let winner = winner.normalized_path().as_ref();
+        let real = |path: &Path| dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if real(shadowed) == real(winner) {
+            return false;
+        }
+        context.report(shadowed_diagnostic(shadowed, winner));
+        true
+    }
+
+    /// Returns the directories which would be searched for the bare specifier
+    /// requested from `dir`, like `require.resolve.paths()` in Node.js.
+    pub fn lookup_paths(&self, dir: &Path) -> Vec<PathBuf> {
+        let dir = NormalizedPath::new(dir);
+        let dir = dir.as_ref();
+        let (absolute, relative): (Vec<_>, Vec<_>) = self
+            .options
+            .modules
+            .iter()
+            .map(Path::new)
+            .partition(|module| module.is_absolute());
+        let mut paths: Vec<PathBuf> =
+            std::iter::successors(Some(dir), |dir| self.modules_parent_dir(dir))
+                .flat_map(|ancestor| relative.iter().map(move |module| ancestor.join(module)))
+                .chain(absolute.into_iter().map(Path::to_path_buf))
+                .chain(self.global_dirs.iter().cloned())
+                .collect();
+        let mut visited = std::collections::HashSet::new();
+        paths.retain(|path| visited.insert(path.clone()));
+        paths
+    }
+
+    /// The next directory to look up `modules`, it returns `None` when
+    /// `dir` is the root or `module_lookup_boundary`.
+    fn modules_parent_dir<'a>(&self, dir: &'a Path) -> Option<&'a Path> {
+        match self.options.module_lookup_boundary.as_deref() {
+            Some(boundary) if dir == boundary => None,
+            _ => dir.parent(),
+        }
+    }
+
     fn _resolve_as_modules(
         &self,
         info: Info,
@@ -169,13 +363,15 @@ impl Resolver {
         context: &mut Context,
     ) -> State {
         let entry = self.load_entry(node_modules_path);
-        let pkg_info = match entry.pkg_info(self) {
-            Ok(pkg_info) => pkg_info.as_ref(),
+        let pkg_info = match entry.pkg_info(self, context) {
+            Ok(pkg_info) => pkg_info,
             Err(err) => return State::Error(err),
         };
+        let pkg_info = pkg_info.as_ref();
         let state = if entry.is_dir() {
             // is there had `node_modules` folder?
             self.resolve_node_modules(info, node_modules_path, context)
+                .then(|info| self.resolve_types_package(info, node_modules_path, context))
                 .then(|info| {
                     let is_resolve_self = pkg_info.map_or(false, |pkg_info| {
                         let request_module_name =
@@ -223,27 +419,16 @@ impl Resolver {
                 State::Resolving(info)
             }
         } else {
-            let pkg_info = match entry.pkg_info(self) {
+            let pkg_info = match entry.pkg_info(self, context) {
                 Ok(pkg_info) => pkg_info,
                 Err(err) => return State::Error(err),
             };
-            let state = if let Some(pkg_info) = pkg_info {
+            let pkg_info = pkg_info.as_deref();
+            let use_exports = pkg_info.map_or(false, |pkg_info| {
                 let out_node_modules = pkg_info.dir().eq(original_dir);
-                if !out_node_modules || is_resolve_self(pkg_info, request_module_name) {
-                    ExportsFieldPlugin::new(pkg_info).apply(self, module_info, context)
-                } else {
-                    State::Resolving(module_info)
-                }
-                .then(|info| ImportsFieldPlugin::new(pkg_info).apply(self, info, context))
-                .then(|info| MainFieldPlugin::new(pkg_info).apply(self, info, context))
-                .then(|info| BrowserFieldPlugin::new(pkg_info, true).apply(self, info, context))
-            } else {
-                State::Resolving(module_info)
-            }
-            .then(|info| self.resolve_as_context(info, context))
-            .then(|info| self.resolve_as_fully_specified(info, context))
-            .then(|info| self.resolve_as_file(info, context))
-            .then(|info| self.resolve_as_dir(info, context));
+                !out_node_modules || is_resolve_self(pkg_info, request_module_name)
+            });
+            let state = self.resolve_package(module_info, pkg_info, use_exports, context);
 
             match state {
                 State::Failed(info) => State::Resolving(info),
@@ -251,6 +436,111 @@ impl Resolver {
             }
         }
     }
+
+    fn resolve_package(
+        &self,
+        module_info: Info,
+        pkg_info: Option<&DescriptionData>,
+        use_exports: bool,
+        context: &mut Context,
+    ) -> State {
+        if let Some(pkg_info) = pkg_info {
+            if use_exports {
+                ExportsFieldPlugin::new(pkg_info).apply(self, module_info, context)
+            } else {
+                State::Resolving(module_info)
+            }
+            .then(|info| ImportsFieldPlugin::new(pkg_info).apply(self, info, context))
+            .then(|info| TypesVersionsPlugin::new(pkg_info).apply(self, info, context))
+            .then(|info| MainFieldPlugin::new(pkg_info).apply(self, info, context))
+            .then(|info| AliasFieldPlugin::new(pkg_info, true).apply(self, info, context))
+        } else {
+            State::Resolving(module_info)
+        }
+        .then(|info| self.resolve_as_context(info, context))
+        .then(|info| self.resolve_as_fully_specified(info, context))
+        .then(|info| self.resolve_as_file(info, context))
+        .then(|info| self.resolve_as_dir(info, context))
+    }
+
+    /// Resolve the request in the package located at `pkg_dir` directly,
+    /// such as a workspace package.
+    pub(crate) fn resolve_package_dir(
+        &self,
+        info: Info,
+        pkg_dir: &Path,
+        context: &mut Context,
+    ) -> State {
+        let entry = self.load_entry(pkg_dir);
+        if !entry.is_dir() {
+            return State::Resolving(info);
+        }
+        let pkg_info = match entry.pkg_info(self, context) {
+            Ok(pkg_info) => pkg_info,
+            Err(err) => return State::Error(err),
+        };
+        let pkg_info = pkg_info
+            .as_deref()
+            .filter(|pkg_info| pkg_info.dir().as_ref() == pkg_dir);
+        let target = info.request().target();
+        let subpath = get_path_from_request(target)
+            .map_or(Cow::Borrowed("."), |path| Cow::Owned(format!(".{path}")));
+        let module_info = Info::new(pkg_dir, info.request().clone()).with_target(&subpath);
+        let state = self.resolve_package(module_info, pkg_info, true, context);
+        if state.is_finished() {
+            state
+        } else {
+            State::Resolving(info)
+        }
+    }
+
+    fn resolve_workspace_package(&self, info: Info, context: &mut Context) -> State {
+        // only the importers inside the workspace can see its packages.
+        let is_inside_workspace = self.options.workspace.as_ref().map_or(false, |root| {
+            info.normalized_path().as_ref().starts_with(root)
+        });
+        if !is_inside_workspace {
+            return State::Resolving(info);
+        }
+        let packages = match self.workspace_packages(context) {
+            Ok(Some(packages)) => packages,
+            Ok(None) => return State::Resolving(info),
+            Err(err) => return State::Error(err),
+        };
+        let module_name = get_module_name_from_request(info.request().target());
+        match packages.get(module_name) {
+            Some(pkg_dir) => {
+                tracing::debug!(
+                    "Resolving '{}' from workspace package '{}'",
+                    color::blue(&info.request().target()),
+                    color::blue(&pkg_dir.display())
+                );
+                self.resolve_package_dir(info, pkg_dir, context)
+            }
+            None => State::Resolving(info),
+        }
+    }
+
+    /// Try `node_modules/@types/<name>` when the package itself
+    /// can not be found in `node_modules_path`.
+    fn resolve_types_package(
+        &self,
+        info: Info,
+        node_modules_path: &Path,
+        context: &mut Context,
+    ) -> State {
+        let target = info.request().target();
+        if !self.options.types || target.starts_with("@types/") {
+            return State::Resolving(info);
+        }
+        let types_info = info.clone().with_target(&get_types_package_request(target));
+        let state = self.resolve_node_modules(types_info, node_modules_path, context);
+        if state.is_finished() {
+            state
+        } else {
+            State::Resolving(info)
+        }
+    }
 }
 
 fn is_resolve_self(pkg_info: &DescriptionData, request_module_name: &str) -> bool {
@@ -276,17 +566,85 @@ pub(crate) fn split_slash_from_request(target: &str) -> Option<usize> {
     .copied()
 }
 
-fn get_module_name_from_request(target: &str) -> &str {
+/// The global folders of Node.js, see
+/// <https://nodejs.org/api/modules.html#loading-from-the-global-folders>.
+pub(crate) fn node_global_dirs(
+    node_path: Option<std::ffi::OsString>,
+    home: Option<std::ffi::OsString>,
+    prefix: Option<std::ffi::OsString>,
+) -> Vec<PathBuf> {
+    let node_path = node_path
+        .map(|node_path| std::env::split_paths(&node_path).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|path| !path.as_os_str().is_empty())
+        .map(|path| {
+            use path_absolutize::Absolutize;
+            // relative entries are resolved from the current working directory.
+            path.absolutize()
+                .map_or(path.clone(), |path| path.to_path_buf())
+        });
+    let home = home
+        .map(PathBuf::from)
+        .map(|home| [home.join(".node_modules"), home.join(".node_libraries")])
+        .into_iter()
+        .flatten();
+    let prefix = prefix.map(|prefix| PathBuf::from(prefix).join("lib").join("node"));
+    node_path.chain(home).chain(prefix).collect()
+}
+
+fn shadowed_diagnostic(shadowed: &Path, winner: &Path) -> Diagnostic {
+    Diagnostic::warning(
+        format!(
+            "'{}' also matches, but it is shadowed by '{}'",
+            shadowed.display(),
+            winner.display()
+        ),
+        shadowed.to_path_buf(),
+    )
+}
+
+pub(crate) fn get_module_name_from_request(target: &str) -> &str {
     split_slash_from_request(target).map_or(target, |index| &target[0..index])
 }
 
+/// `@scope/pkg/path` -> `@types/scope__pkg/path`
+fn get_types_package_request(target: &str) -> String {
+    let module_name = get_module_name_from_request(target);
+    let path = &target[module_name.len()..];
+    let mangled = module_name
+        .strip_prefix('@')
+        .map_or(Cow::Borrowed(module_name), |scoped| {
+            Cow::Owned(scoped.replacen('/', "__", 1))
+        });
+    format!("@types/{mangled}{path}")
+}
+
 pub(crate) fn get_path_from_request(target: &str) -> Option<Cow<str>> {
     split_slash_from_request(target).map(|index| Cow::Borrowed(&target[index..]))
 }
 
+/// The extension of file `name`, the declaration extensions such as
+/// `.d.ts` are treated as a whole.
+fn file_extension(name: &str) -> Option<&str> {
+    DECLARATION_EXTENSIONS
+        .iter()
+        .map(|(_, declaration)| *declaration)
+        .find(|declaration| name.len() > declaration.len() && name.ends_with(declaration))
+        .or_else(|| {
+            name.rfind('.')
+                .filter(|index| *index > 0)
+                .map(|index| &name[index..])
+        })
+}
+
 #[cfg(test)]
 mod test {
-    use super::{get_module_name_from_request, get_path_from_request, split_slash_from_request};
+    use super::{
+        file_extension, get_module_name_from_request, get_path_from_request,
+        get_types_package_request, node_global_dirs, split_slash_from_request,
+    };
+    use std::path::PathBuf;
 
     #[test]
     fn test_split_slash_from_request() {
@@ -314,4 +672,38 @@ mod test {
         assert_eq!(get_path_from_request("@a/b"), None);
         assert_eq!(get_path_from_request("@a/b/c"), Some("/c".into()));
     }
+
+    #[test]
+    fn test_node_global_dirs() {
+        assert!(node_global_dirs(None, None, None).is_empty());
+        let node_path = std::env::join_paths(["/a", "", "/b"]).unwrap();
+        assert_eq!(
+            node_global_dirs(Some(node_path), Some("/home".into()), Some("/usr".into())),
+            vec![
+                PathBuf::from("/a"),
+                PathBuf::from("/b"),
+                PathBuf::from("/home/.node_modules"),
+                PathBuf::from("/home/.node_libraries"),
+                PathBuf::from("/usr/lib/node"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_types_package_request() {
+        assert_eq!(get_types_package_request("a"), "@types/a");
+        assert_eq!(get_types_package_request("a/b"), "@types/a/b");
+        assert_eq!(get_types_package_request("@a/b"), "@types/a__b");
+        assert_eq!(get_types_package_request("@a/b/c"), "@types/a__b/c");
+    }
+
+    #[test]
+    fn test_file_extension() {
+        assert_eq!(file_extension("foo.js"), Some(".js"));
+        assert_eq!(file_extension("foo.d.ts"), Some(".d.ts"));
+        assert_eq!(file_extension("foo.ios.d.mts"), Some(".d.mts"));
+        assert_eq!(file_extension(".d.ts"), Some(".ts"));
+        assert_eq!(file_extension(".eslintrc"), None);
+        assert_eq!(file_extension("LICENSE"), None);
+    }
 }
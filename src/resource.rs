@@ -1,4 +1,4 @@
-use crate::{description::DescriptionData, info::Info, Resolver};
+use crate::{description::DescriptionData, info::Info, Context, Diagnostic, RResult, Resolver};
 use std::{path::PathBuf, sync::Arc};
 
 #[derive(Debug, Clone)]
@@ -7,24 +7,25 @@ pub struct Resource {
     pub query: Option<String>,
     pub fragment: Option<String>,
     pub description: Option<Arc<DescriptionData>>,
+    /// The diagnostics reported during the resolving, such as the version
+    /// mismatch of dependencies, they are the same as the ones returned
+    /// by `Resolver::resolve_with_diagnostics`.
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl Resource {
-    pub(crate) fn new(info: Info, resolver: &Resolver) -> Self {
+    pub(crate) fn new(info: Info, resolver: &Resolver, context: &mut Context) -> RResult<Self> {
         let path = info.normalized_path().as_ref().to_path_buf();
         let query = info.request().query();
         let fragment = info.request().fragment();
-        let description = resolver
-            .load_entry(&path)
-            .pkg_info(resolver)
-            .unwrap()
-            .clone();
-        Resource {
+        let description = resolver.load_entry(&path).pkg_info(resolver, context)?;
+        Ok(Resource {
             path,
             query: (!query.is_empty()).then(|| query.into()),
             fragment: (!fragment.is_empty()).then(|| fragment.into()),
             description,
-        }
+            diagnostics: context.diagnostics.clone(),
+        })
     }
 
     pub fn join(&self) -> PathBuf {
@@ -0,0 +1,368 @@
+//! A small subset of https://github.com/npm/node-semver, enough to
+//! evaluate the version ranges written in description files.
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    /// Dot separated pre-release identifiers, empty for a release.
+    pre: Vec<Box<str>>,
+}
+
+impl Version {
+    /// Parse a full or partial version such as `1.2.3`, `v1.2.3-beta.1`
+    /// or `4.2`, missing parts are filled with `0`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let partial = Partial::parse(input)?;
+        Some(partial.floor())
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: vec![],
+        }
+    }
+
+    fn same_tuple(&self, other: &Self) -> bool {
+        self.major == other.major && self.minor == other.minor && self.patch == other.patch
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => compare_pre(&self.pre, &other.pre),
+            })
+    }
+}
+
+fn compare_pre(a: &[Box<str>], b: &[Box<str>]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// A version whose trailing parts may be omitted or written as `x`/`*`.
+#[derive(Debug, Clone, Default)]
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Vec<Box<str>>,
+}
+
+impl Partial {
+    fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let input = input
+            .strip_prefix('v')
+            .or_else(|| input.strip_prefix('='))
+            .unwrap_or(input)
+            .trim_start();
+        // build metadata never takes part in the comparison.
+        let input = input.split('+').next().unwrap_or_default();
+        let (numbers, pre) = match input.split_once('-') {
+            Some((numbers, pre)) => (numbers, pre.split('.').map(Box::from).collect()),
+            None => (input, vec![]),
+        };
+        let mut parts = [None; 3];
+        let mut wildcard = false;
+        for (index, part) in numbers.split('.').enumerate() {
+            if index > 2 {
+                return None;
+            }
+            if matches!(part, "x" | "X" | "*") || wildcard {
+                wildcard = true;
+                continue;
+            }
+            parts[index] = Some(part.parse::<u64>().ok()?);
+        }
+        if numbers.is_empty() {
+            parts = [None; 3];
+        }
+        Some(Self {
+            major: parts[0],
+            minor: parts[1],
+            patch: parts[2],
+            pre,
+        })
+    }
+
+    fn floor(&self) -> Version {
+        Version {
+            major: self.major.unwrap_or(0),
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+        }
+    }
+
+    /// The smallest version which is greater than every version matching `self`,
+    /// returns `None` if the version number overflows.
+    fn ceil(&self) -> Option<Option<Version>> {
+        Some(match (self.major, self.minor, self.patch) {
+            (None, _, _) => None,
+            (Some(major), None, _) => Some(Version::new(major.checked_add(1)?, 0, 0)),
+            (Some(major), Some(minor), None) => Some(Version::new(major, minor.checked_add(1)?, 0)),
+            (Some(_), Some(_), Some(_)) => None,
+        })
+    }
+
+    fn is_complete(&self) -> bool {
+        self.patch.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Equal,
+}
+
+#[derive(Debug, Clone)]
+struct Comparator {
+    operator: Operator,
+    version: Version,
+}
+
+impl Comparator {
+    fn new(operator: Operator, version: Version) -> Self {
+        Self { operator, version }
+    }
+
+    fn test(&self, version: &Version) -> bool {
+        let ordering = version.cmp(&self.version);
+        match self.operator {
+            Operator::Greater => ordering.is_gt(),
+            Operator::GreaterEqual => ordering.is_ge(),
+            Operator::Less => ordering.is_lt(),
+            Operator::LessEqual => ordering.is_le(),
+            Operator::Equal => ordering.is_eq(),
+        }
+    }
+}
+
+/// A set of comparators such as `>=1.2.7 <1.3.0 || ^2`.
+#[derive(Debug, Clone)]
+pub struct Range {
+    /// Satisfied when any of the inner list is fully satisfied.
+    set: Vec<Vec<Comparator>>,
+}
+
+impl Range {
+    pub fn parse(input: &str) -> Option<Self> {
+        let set = input
+            .split("||")
+            .map(parse_comparators)
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { set })
+    }
+
+    pub fn satisfies(&self, version: &Version) -> bool {
+        self.set.iter().any(|comparators| {
+            if !comparators
+                .iter()
+                .all(|comparator| comparator.test(version))
+            {
+                return false;
+            }
+            // Pre-release versions only match a range which explicitly
+            // mentions a pre-release of the same `major.minor.patch`.
+            !version.is_prerelease()
+                || comparators.iter().any(|comparator| {
+                    comparator.version.is_prerelease() && comparator.version.same_tuple(version)
+                })
+        })
+    }
+}
+
+fn parse_comparators(input: &str) -> Option<Vec<Comparator>> {
+    let input = input.trim();
+    if let Some((from, to)) = input.split_once(" - ") {
+        let from = Partial::parse(from)?;
+        let to = Partial::parse(to)?;
+        let mut comparators = vec![Comparator::new(Operator::GreaterEqual, from.floor())];
+        if let Some(ceil) = to.ceil()? {
+            comparators.push(Comparator::new(Operator::Less, ceil));
+        } else if to.is_complete() {
+            comparators.push(Comparator::new(Operator::LessEqual, to.floor()));
+        }
+        return Some(comparators);
+    }
+
+    let mut comparators = vec![];
+    let mut tokens = input.split_whitespace().peekable();
+    while let Some(token) = tokens.next() {
+        // allow a space between the operator and the version, such as `>= 1.2`
+        let token = if matches!(token, ">" | ">=" | "<" | "<=" | "=" | "~" | "^") {
+            format!("{token}{}", tokens.next()?)
+        } else {
+            token.to_string()
+        };
+        parse_comparator(&token, &mut comparators)?;
+    }
+    Some(comparators)
+}
+
+fn parse_comparator(token: &str, comparators: &mut Vec<Comparator>) -> Option<()> {
+    let (operator, rest) = [">=", "<=", ">", "<", "=", "~>", "~", "^"]
+        .iter()
+        .find_map(|operator| token.strip_prefix(operator).map(|rest| (*operator, rest)))
+        .unwrap_or(("", token));
+    let partial = Partial::parse(rest)?;
+    let floor = partial.floor();
+    match operator {
+        ">=" => comparators.push(Comparator::new(Operator::GreaterEqual, floor)),
+        "<" => comparators.push(Comparator::new(Operator::Less, floor)),
+        ">" => match partial.ceil()? {
+            Some(ceil) => comparators.push(Comparator::new(Operator::GreaterEqual, ceil)),
+            None if partial.major.is_none() => {
+                comparators.push(Comparator::new(Operator::Less, Version::new(0, 0, 0)))
+            }
+            None => comparators.push(Comparator::new(Operator::Greater, floor)),
+        },
+        "<=" => match partial.ceil()? {
+            Some(ceil) => comparators.push(Comparator::new(Operator::Less, ceil)),
+            None if partial.major.is_none() => {}
+            None => comparators.push(Comparator::new(Operator::LessEqual, floor)),
+        },
+        "~" | "~>" => {
+            comparators.push(Comparator::new(Operator::GreaterEqual, floor.clone()));
+            let ceil = match (partial.major, partial.minor) {
+                (None, _) => None,
+                (Some(major), None) => Some(Version::new(major.checked_add(1)?, 0, 0)),
+                (Some(major), Some(minor)) => Some(Version::new(major, minor.checked_add(1)?, 0)),
+            };
+            if let Some(ceil) = ceil {
+                comparators.push(Comparator::new(Operator::Less, ceil));
+            }
+        }
+        "^" => {
+            comparators.push(Comparator::new(Operator::GreaterEqual, floor.clone()));
+            let ceil = match (partial.major, partial.minor, partial.patch) {
+                (None, _, _) => None,
+                (Some(0), Some(0), Some(patch)) => Some(Version::new(0, 0, patch.checked_add(1)?)),
+                (Some(0), Some(minor), _) => Some(Version::new(0, minor.checked_add(1)?, 0)),
+                (Some(major), _, _) => Some(Version::new(major.checked_add(1)?, 0, 0)),
+            };
+            if let Some(ceil) = ceil {
+                comparators.push(Comparator::new(Operator::Less, ceil));
+            }
+        }
+        _ => {
+            if partial.is_complete() {
+                comparators.push(Comparator::new(Operator::Equal, floor));
+            } else if let Some(ceil) = partial.ceil()? {
+                comparators.push(Comparator::new(Operator::GreaterEqual, floor));
+                comparators.push(Comparator::new(Operator::Less, ceil));
+            }
+        }
+    }
+    Some(())
+}
+
+#[test]
+fn version_test() {
+    let v = |s: &str| Version::parse(s).unwrap();
+    assert_eq!(v("1.2.3").to_string(), "1.2.3");
+    assert_eq!(v("v4.2").to_string(), "4.2.0");
+    assert_eq!(v("1.0.0-beta.2+build").to_string(), "1.0.0-beta.2");
+    assert!(v("1.0.0-alpha") < v("1.0.0-alpha.1"));
+    assert!(v("1.0.0-alpha.1") < v("1.0.0-alpha.beta"));
+    assert!(v("1.0.0-beta.2") < v("1.0.0-beta.11"));
+    assert!(v("1.0.0-rc.1") < v("1.0.0"));
+    assert!(v("1.10.0") > v("1.9.9"));
+    assert!(Version::parse("a.b").is_none());
+}
+
+#[test]
+fn range_test() {
+    fn satisfies(range: &str, version: &str) -> bool {
+        Range::parse(range)
+            .unwrap()
+            .satisfies(&Version::parse(version).unwrap())
+    }
+    assert!(satisfies("*", "5.2.2"));
+    assert!(satisfies("", "0.0.1"));
+    assert!(satisfies(">=4.2", "4.2.0"));
+    assert!(!satisfies(">=4.2", "4.1.9"));
+    assert!(satisfies(">4.2", "4.3.0"));
+    assert!(!satisfies(">4.2", "4.2.5"));
+    assert!(satisfies("<=4.2", "4.2.5"));
+    assert!(!satisfies("<4.2", "4.2.0"));
+    assert!(satisfies(">= 3.1 < 4", "3.9.0"));
+    assert!(!satisfies(">= 3.1 < 4", "4.0.0"));
+    assert!(satisfies("1.x", "1.9.0"));
+    assert!(!satisfies("1.x", "2.0.0"));
+    assert!(satisfies("1.2", "1.2.9"));
+    assert!(satisfies("1.2.3", "1.2.3"));
+    assert!(!satisfies("=1.2.3", "1.2.4"));
+    assert!(satisfies("^1.2.3", "1.9.0"));
+    assert!(!satisfies("^1.2.3", "2.0.0"));
+    assert!(!satisfies("^1.2.3", "1.2.2"));
+    assert!(satisfies("^0.2.3", "0.2.9"));
+    assert!(!satisfies("^0.2.3", "0.3.0"));
+    assert!(!satisfies("^0.0.3", "0.0.4"));
+    assert!(satisfies("~1.2.3", "1.2.9"));
+    assert!(!satisfies("~1.2.3", "1.3.0"));
+    assert!(satisfies("~1", "1.9.0"));
+    assert!(satisfies("1.2 - 2.3", "2.3.9"));
+    assert!(!satisfies("1.2 - 2.3", "2.4.0"));
+    assert!(satisfies("1.2.3 - 2.3.4", "2.3.4"));
+    assert!(satisfies("<1 || >=3", "3.1.0"));
+    assert!(!satisfies("<1 || >=3", "2.0.0"));
+    assert!(!satisfies("^1.0.0", "1.1.0-beta"));
+    assert!(satisfies("^1.1.0-alpha", "1.1.0-beta"));
+    assert!(Range::parse(">=abc").is_none());
+    // the upper bound overflows.
+    assert!(Range::parse("^18446744073709551615").is_none());
+    assert!(Range::parse("~1.18446744073709551615").is_none());
+    assert!(Range::parse("18446744073709551615.x").is_none());
+    assert!(satisfies(
+        ">=18446744073709551615",
+        "18446744073709551615.0.0"
+    ));
+}
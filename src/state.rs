@@ -2,7 +2,9 @@ use crate::{Error, Info, ResolveResult};
 
 #[derive(Debug)]
 pub enum State {
+    /// The request is resolved.
     Success(ResolveResult<Info>),
+    /// Continue the resolving.
     Resolving(Info),
     /// return error directly
     Error(Error),
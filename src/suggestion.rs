@@ -0,0 +1,233 @@
+use crate::{
+    resolve::{get_module_name_from_request, get_path_from_request},
+    Context, PathKind, Resolver,
+};
+use std::path::{Path, PathBuf};
+
+/// The max number of suggestions for a failed resolving.
+const MAX_SUGGESTIONS: usize = 5;
+
+impl Resolver {
+    /// Returns the "did you mean" candidates for `request` which can't be
+    /// resolved in `path` and where they come from, the nearest candidate
+    /// is the first.
+    pub(crate) fn suggest(
+        &self,
+        path: &Path,
+        request: &str,
+        context: &mut Context,
+    ) -> Vec<(String, PathBuf)> {
+        let request = Self::parse(request);
+        let target = request.target();
+        let mut candidates = match request.kind() {
+            PathKind::Relative | PathKind::AbsolutePosix | PathKind::AbsoluteWin => {
+                self.suggest_siblings(&path.join(target), target)
+            }
+            PathKind::Internal => self.suggest_imports(path, target, context),
+            PathKind::Normal => self.suggest_modules(path, target, context),
+        };
+        candidates.sort_by(|(a, a_suggestion, _), (b, b_suggestion, _)| {
+            a.cmp(b).then_with(|| a_suggestion.cmp(b_suggestion))
+        });
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, suggestion, location)| (suggestion, location))
+            .collect()
+    }
+
+    /// The keys of `imports` field in the description file of `path`.
+    fn suggest_imports(
+        &self,
+        path: &Path,
+        target: &str,
+        context: &mut Context,
+    ) -> Vec<(usize, String, PathBuf)> {
+        let Ok(Some(pkg_info)) = self.load_entry(path).pkg_info(self, context) else {
+            return vec![];
+        };
+        let Some(imports) = self
+            .options
+            .imports_field
+            .iter()
+            .find_map(|field| pkg_info.data().get_filed(field))
+            .and_then(|imports| imports.as_object())
+        else {
+            return vec![];
+        };
+        imports
+            .keys()
+            // the pattern keys can't be imported as they are.
+            .filter(|key| key.starts_with('#') && !key.contains('*'))
+            .map(|key| {
+                (
+                    edit_distance(key, target),
+                    key.to_string(),
+                    pkg_info.path().to_path_buf(),
+                )
+            })
+            .collect()
+    }
+
+    /// The packages with similar names in `node_modules`, or the exported
+    /// subpaths once the package exists.
+    fn suggest_modules(
+        &self,
+        path: &Path,
+        target: &str,
+        context: &mut Context,
+    ) -> Vec<(usize, String, PathBuf)> {
+        let module_name = get_module_name_from_request(target);
+        let subpath = get_path_from_request(target).unwrap_or_default();
+        let lookup_paths = self.lookup_paths(path);
+        if let Some(pkg_dir) = lookup_paths
+            .iter()
+            .map(|modules| modules.join(module_name))
+            .find(|pkg_dir| self.load_entry(pkg_dir).is_dir())
+        {
+            let candidates = self.suggest_exports(&pkg_dir, module_name, &subpath, context);
+            return if candidates.is_empty() && !subpath.is_empty() {
+                self.suggest_siblings(&pkg_dir.join(&subpath[1..]), target)
+            } else {
+                candidates
+            };
+        }
+        let mut candidates = vec![];
+        for modules in lookup_paths {
+            for name in self.package_names(&modules) {
+                let distance = edit_distance(&name, module_name);
+                if is_similar(distance, module_name)
+                    && !candidates.iter().any(|(_, found, _)| *found == name)
+                {
+                    let location = modules.join(&name);
+                    candidates.push((distance, format!("{name}{subpath}"), location));
+                }
+            }
+        }
+        candidates
+    }
+
+    fn suggest_exports(
+        &self,
+        pkg_dir: &Path,
+        module_name: &str,
+        subpath: &str,
+        context: &mut Context,
+    ) -> Vec<(usize, String, PathBuf)> {
+        let Ok(Some(pkg_info)) = self.load_entry(pkg_dir).pkg_info(self, context) else {
+            return vec![];
+        };
+        let Some(exports) = self
+            .options
+            .exports_field
+            .iter()
+            .find_map(|field| pkg_info.data().get_filed(field))
+            .and_then(|exports| exports.as_object())
+        else {
+            return vec![];
+        };
+        let subpath = format!(".{subpath}");
+        exports
+            .keys()
+            .filter(|key| key.starts_with('.') && !key.contains('*'))
+            .map(|key| {
+                let suggestion = format!("{module_name}{}", &key[1..]);
+                (
+                    edit_distance(key, &subpath),
+                    suggestion,
+                    pkg_info.path().to_path_buf(),
+                )
+            })
+            .collect()
+    }
+
+    /// The files in the same directory which have a similar name or
+    /// the same name with different extension.
+    fn suggest_siblings(&self, path: &Path, target: &str) -> Vec<(usize, String, PathBuf)> {
+        let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+            return vec![];
+        };
+        let name = name.to_string_lossy();
+        let stem = file_stem(&name);
+        let Some(prefix) = target.strip_suffix(name.as_ref()) else {
+            return vec![];
+        };
+        let Some(siblings) = self.load_entry(dir).children() else {
+            return vec![];
+        };
+        siblings
+            .iter()
+            .filter_map(|sibling| {
+                let sibling = sibling.to_string_lossy();
+                let distance = if file_stem(&sibling) == stem {
+                    0
+                } else {
+                    edit_distance(&sibling, &name).min(edit_distance(file_stem(&sibling), stem))
+                };
+                (sibling != name && is_similar(distance, stem))
+                    .then(|| (distance, format!("{prefix}{sibling}"), dir.join(&*sibling)))
+            })
+            .collect()
+    }
+
+    /// The names of packages in `modules`, includes the scoped packages.
+    fn package_names(&self, modules: &Path) -> Vec<String> {
+        let Some(children) = self.load_entry(modules).children() else {
+            return vec![];
+        };
+        let mut names = vec![];
+        for name in children.iter() {
+            let name = name.to_string_lossy();
+            if name.starts_with('@') {
+                let Some(scoped) = self.load_entry(&modules.join(&*name)).children() else {
+                    continue;
+                };
+                names.extend(
+                    scoped
+                        .iter()
+                        .map(|scoped| format!("{name}/{}", scoped.to_string_lossy())),
+                );
+            } else if !name.starts_with('.') {
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        names
+    }
+}
+
+fn file_stem(name: &str) -> &str {
+    name.find('.')
+        .filter(|index| *index > 0)
+        .map_or(name, |index| &name[..index])
+}
+
+fn is_similar(distance: usize, name: &str) -> bool {
+    distance <= 2 && distance < name.chars().count()
+}
+
+/// The levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let cost = usize::from(a != *b);
+            current.push((prev[j] + cost).min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+#[test]
+fn edit_distance_test() {
+    assert_eq!(edit_distance("foo", "foo"), 0);
+    assert_eq!(edit_distance("foo", "fo"), 1);
+    assert_eq!(edit_distance("foo", "bar"), 3);
+    assert_eq!(edit_distance("react", "raect"), 2);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(file_stem("index.d.ts"), "index");
+    assert_eq!(file_stem(".eslintrc"), ".eslintrc");
+}
@@ -1,7 +1,7 @@
 // copy from https://github.com/drivasperez/tsconfig
 
 use crate::context::Context;
-use crate::{Error, Info, RResult, ResolveResult, Resolver, State};
+use crate::{Diagnostic, Error, Info, RResult, ResolveResult, Resolver, State};
 use rustc_hash::FxHashMap;
 use std::{path::Path, sync::Arc};
 
@@ -15,6 +15,13 @@ pub struct TsConfig {
 pub struct CompilerOptions {
     pub base_url: Option<String>,
     pub paths: Option<FxHashMap<String, Vec<String>>>,
+    pub custom_conditions: Option<Vec<String>>,
+    pub module_suffixes: Option<Vec<String>>,
+    pub allow_importing_ts_extensions: Option<bool>,
+    pub resolve_json_module: Option<bool>,
+    pub out_dir: Option<String>,
+    pub root_dir: Option<String>,
+    pub declaration_dir: Option<String>,
 }
 
 impl TsConfig {
@@ -55,7 +62,34 @@ impl Resolver {
                 }
                 map
             });
-            CompilerOptions { base_url, paths }
+            let string_list = |key: &str| {
+                options.get(key).and_then(|v| v.as_array()).map(|list| {
+                    list.iter()
+                        .filter_map(|v| v.as_str().map(String::from))
+                        .collect()
+                })
+            };
+            let custom_conditions = string_list("customConditions");
+            let module_suffixes = string_list("moduleSuffixes");
+            let allow_importing_ts_extensions = options
+                .get("allowImportingTsExtensions")
+                .and_then(|v| v.as_bool());
+            let resolve_json_module = options.get("resolveJsonModule").and_then(|v| v.as_bool());
+            let string = |key: &str| options.get(key).and_then(|v| v.as_str()).map(String::from);
+            let out_dir = string("outDir");
+            let root_dir = string("rootDir");
+            let declaration_dir = string("declarationDir");
+            CompilerOptions {
+                base_url,
+                paths,
+                custom_conditions,
+                module_suffixes,
+                allow_importing_ts_extensions,
+                resolve_json_module,
+                out_dir,
+                root_dir,
+                declaration_dir,
+            }
         });
         let extends: Option<String> = json.get("extends").map(|v| v.to_string());
         Ok(TsConfig {
@@ -95,7 +129,12 @@ impl Resolver {
             if let State::Success(result) = state {
                 let extends_tsconfig_json = match result {
                     ResolveResult::Resource(info) => {
-                        self.parse_file_to_value(&info.to_resolved_path(), context)
+                        let extends_location = info.to_resolved_path();
+                        self.parse_file_to_value(&extends_location, context)
+                            .map(|mut json| {
+                                resolve_output_dirs(&mut json, extends_location.parent().unwrap());
+                                json
+                            })
                     }
                     ResolveResult::Ignored => {
                         return Err(Error::UnexpectedValue(format!(
@@ -105,12 +144,39 @@ impl Resolver {
                     }
                 }?;
                 merge(&mut json, extends_tsconfig_json);
+            } else {
+                context.report(
+                    Diagnostic::warning(
+                        format!("Can't resolve '{s}' in the \"extends\" field, it is skipped"),
+                        location.to_path_buf(),
+                    )
+                    .with_key_path(vec![String::from("extends")]),
+                );
             }
         }
         Ok(json)
     }
 }
 
+/// The output directories are relative to the tsconfig which defines them,
+/// rather than the one which extends it.
+fn resolve_output_dirs(json: &mut serde_json::Value, dir: &Path) {
+    let Some(options) = json
+        .get_mut("compilerOptions")
+        .and_then(|options| options.as_object_mut())
+    else {
+        return;
+    };
+    for key in ["outDir", "rootDir", "declarationDir"] {
+        if let Some(serde_json::Value::String(value)) = options.get_mut(key) {
+            // `${configDir}` is kept for the tsconfig which is used.
+            if !value.starts_with("${configDir}") {
+                *value = dir.join(&value).to_string_lossy().to_string();
+            }
+        }
+    }
+}
+
 fn merge(a: &mut serde_json::Value, b: serde_json::Value) {
     match (a, b) {
         (&mut serde_json::Value::Object(ref mut a), serde_json::Value::Object(b)) => {
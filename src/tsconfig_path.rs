@@ -1,13 +1,46 @@
 // Copy from https://github.com/dividab/tsconfig-paths
 
-use crate::{context::Context, Info, RResult, Resolver, State};
+use crate::{context::Context, options::DECLARATION_EXTENSIONS, Info, RResult, Resolver, State};
 use rustc_hash::FxHashMap;
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[derive(Default, Debug)]
 pub struct TsConfigInfo {
     pub paths: Option<FxHashMap<String, Vec<String>>>,
     pub base_url: Option<String>,
+    /// `condition_names` merged with `customConditions`,
+    /// `None` if there is no `customConditions`.
+    pub condition_names: Option<HashSet<String>>,
+    /// Default is `[""]`
+    pub module_suffixes: Vec<String>,
+    pub allow_importing_ts_extensions: Option<bool>,
+    pub resolve_json_module: Option<bool>,
+}
+
+const TS_EXTENSIONS: [&str; 4] = [".ts", ".tsx", ".mts", ".cts"];
+
+impl TsConfigInfo {
+    /// Returns `false` if the extension of `target` is disabled
+    /// by `allowImportingTsExtensions` or `resolveJsonModule`.
+    pub fn is_allowed_extension(&self, target: &str) -> bool {
+        if self.allow_importing_ts_extensions == Some(false)
+            && TS_EXTENSIONS.iter().any(|ext| target.ends_with(ext))
+            && !DECLARATION_EXTENSIONS
+                .iter()
+                .any(|(_, dts)| target.ends_with(dts))
+        {
+            return false;
+        }
+        self.is_allowed_json() || !target.ends_with(".json")
+    }
+
+    pub fn is_allowed_json(&self) -> bool {
+        self.resolve_json_module != Some(false)
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -41,11 +74,43 @@ impl Resolver {
             .compiler_options
             .as_ref()
             .and_then(|options| options.base_url.clone());
-        let paths = tsconfig.compiler_options.and_then(|options| options.paths);
-        Ok(TsConfigInfo { paths, base_url })
+        let Some(options) = tsconfig.compiler_options else {
+            return Ok(TsConfigInfo {
+                base_url,
+                module_suffixes: vec![String::new()],
+                ..Default::default()
+            });
+        };
+        let condition_names = options.custom_conditions.map(|custom_conditions| {
+            let mut condition_names = self.options.condition_names.clone();
+            condition_names.extend(custom_conditions);
+            condition_names
+        });
+        let module_suffixes = options
+            .module_suffixes
+            .filter(|suffixes| !suffixes.is_empty())
+            .unwrap_or_else(|| vec![String::new()]);
+        Ok(TsConfigInfo {
+            paths: options.paths,
+            base_url,
+            condition_names,
+            module_suffixes,
+            allow_importing_ts_extensions: options.allow_importing_ts_extensions,
+            resolve_json_module: options.resolve_json_module,
+        })
+    }
+
+    /// `condition_names` in options merged with the
+    /// `customConditions` of tsconfig.
+    pub(crate) fn condition_names<'a>(&'a self, context: &'a Context) -> &'a HashSet<String> {
+        context
+            .tsconfig
+            .as_ref()
+            .and_then(|tsconfig| tsconfig.condition_names.as_ref())
+            .unwrap_or(&self.options.condition_names)
     }
 
-    fn match_star<'a>(pattern: &'a str, search: &'a str) -> Option<&'a str> {
+    pub(crate) fn match_star<'a>(pattern: &'a str, search: &'a str) -> Option<&'a str> {
         if search.len() < pattern.len() {
             return None;
         }
@@ -95,9 +160,13 @@ impl Resolver {
         context: &mut Context,
     ) -> State {
         let tsconfig = match self.parse_tsconfig(location, context) {
-            Ok(tsconfig) => tsconfig,
+            Ok(tsconfig) => Arc::new(tsconfig),
             Err(error) => return State::Error(error),
         };
+        if !tsconfig.is_allowed_extension(info.request().target()) {
+            return State::Failed(info);
+        }
+        context.tsconfig = Some(tsconfig.clone());
         let location_dir = location.parent().unwrap();
         let absolute_base_url = if let Some(base_url) = tsconfig.base_url.as_ref() {
             location_dir.join(base_url)
@@ -0,0 +1,204 @@
+use crate::{Context, Error, RResult, Resolver};
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+
+/// Maps the name of workspace package to its directory.
+pub type WorkspacePackages = FxHashMap<String, PathBuf>;
+
+impl Resolver {
+    pub(crate) fn workspace_packages(
+        &self,
+        context: &mut Context,
+    ) -> RResult<Option<&WorkspacePackages>> {
+        let Some(root) = self.options.workspace.as_ref() else {
+            return Ok(None);
+        };
+        self.workspace_packages
+            .get_or_try_init(|| self.load_workspace_packages(root, context))
+            .map(Some)
+    }
+
+    fn load_workspace_packages(
+        &self,
+        root: &Path,
+        context: &mut Context,
+    ) -> RResult<WorkspacePackages> {
+        let patterns = self.read_workspace_patterns(root, context)?;
+        let (excluded, included): (Vec<_>, Vec<_>) = patterns
+            .iter()
+            .map(|pattern| pattern.trim_start_matches("./").trim_end_matches('/'))
+            .partition(|pattern| pattern.starts_with('!'));
+        let excluded: Vec<Vec<&str>> = excluded
+            .iter()
+            .map(|pattern| pattern[1..].trim_start_matches("./").split('/').collect())
+            .collect();
+
+        let mut packages = WorkspacePackages::default();
+        for pattern in included {
+            let segments = pattern.split('/').collect::<Vec<_>>();
+            let mut dirs = vec![];
+            self.expand_dirs(root, &segments, &mut dirs);
+            for dir in dirs {
+                let relative = dir.strip_prefix(root).unwrap_or(&dir);
+                let relative = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>();
+                let relative = relative.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+                if excluded
+                    .iter()
+                    .any(|pattern| match_segments(pattern, &relative))
+                {
+                    continue;
+                }
+                let pkg_info = self.load_entry(&dir).pkg_info(self, context)?;
+                if let Some(name) = pkg_info
+                    .as_ref()
+                    .filter(|pkg_info| pkg_info.dir().as_ref() == dir)
+                    .and_then(|pkg_info| pkg_info.data().name())
+                {
+                    packages
+                        .entry(name.to_string())
+                        .or_insert_with(|| dir.clone());
+                }
+            }
+        }
+        Ok(packages)
+    }
+
+    /// Read the globs from `pnpm-workspace.yaml`, or the `workspaces` in description file.
+    fn read_workspace_patterns(&self, root: &Path, context: &mut Context) -> RResult<Vec<String>> {
+        let pnpm_workspace = root.join("pnpm-workspace.yaml");
+        let entry = self.load_entry(&pnpm_workspace);
+        if entry.is_file() {
+            let content = self
+                .cache
+                .fs
+                .read_file(&pnpm_workspace, entry.cached_stat())?;
+            let yaml: serde_json::Value = serde_yaml::from_str(&content).map_err(|err| {
+                Error::UnexpectedValue(format!(
+                    "Parse {} failed. Error: {err}",
+                    pnpm_workspace.display()
+                ))
+            })?;
+            return Ok(string_list(yaml.get("packages")));
+        }
+
+        let pkg_info = self.load_entry(root).pkg_info(self, context)?;
+        Ok(pkg_info
+            .as_ref()
+            .filter(|pkg_info| pkg_info.dir().as_ref() == root)
+            .and_then(|pkg_info| pkg_info.data().raw().get("workspaces"))
+            .map(|workspaces| {
+                // yarn also accepts `{ "packages": [] }`
+                string_list(workspaces.get("packages").or(Some(workspaces)))
+            })
+            .unwrap_or_default())
+    }
+}
+
+fn string_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|value| value.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|item| item.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Resolver {
+    fn sub_dirs(&self, dir: &Path) -> Vec<PathBuf> {
+        let Some(children) = self.load_entry(dir).children() else {
+            return vec![];
+        };
+        children
+            .iter()
+            .filter(|name| *name != "node_modules")
+            .map(|name| dir.join(name))
+            .filter(|sub_dir| self.load_entry(sub_dir).is_dir())
+            .collect()
+    }
+
+    fn expand_dirs(&self, dir: &Path, segments: &[&str], dirs: &mut Vec<PathBuf>) {
+        match segments.split_first() {
+            None => dirs.push(dir.to_path_buf()),
+            Some((&"**", rest)) => {
+                self.expand_dirs(dir, rest, dirs);
+                for sub_dir in self.sub_dirs(dir) {
+                    self.expand_dirs(&sub_dir, segments, dirs);
+                }
+            }
+            Some((segment, rest)) if segment.contains(['*', '?']) => {
+                for sub_dir in self.sub_dirs(dir) {
+                    let name = sub_dir.file_name().unwrap_or_default().to_string_lossy();
+                    if match_wildcard(segment, &name) {
+                        self.expand_dirs(&sub_dir, rest, dirs);
+                    }
+                }
+            }
+            Some((segment, rest)) => {
+                let sub_dir = dir.join(segment);
+                if self.load_entry(&sub_dir).is_dir() {
+                    self.expand_dirs(&sub_dir, rest, dirs);
+                }
+            }
+        }
+    }
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+        (None, None) => true,
+        (Some((&"**", rest)), _) => {
+            match_segments(rest, path)
+                || path
+                    .split_first()
+                    .map_or(false, |(_, path_rest)| match_segments(pattern, path_rest))
+        }
+        (Some((segment, rest)), Some((name, path_rest))) => {
+            match_wildcard(segment, name) && match_segments(rest, path_rest)
+        }
+        _ => false,
+    }
+}
+
+/// Matches a path segment with `*` and `?`.
+fn match_wildcard(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let name = name.as_bytes();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+#[test]
+fn match_wildcard_test() {
+    assert!(match_wildcard("*", "foo"));
+    assert!(match_wildcard("foo-*", "foo-bar"));
+    assert!(match_wildcard("*-bar", "foo-bar"));
+    assert!(match_wildcard("f?o", "foo"));
+    assert!(!match_wildcard("foo-*", "bar-foo"));
+    assert!(match_segments(
+        &["**", "excluded"],
+        &["packages", "excluded"]
+    ));
+    assert!(match_segments(&["packages", "*"], &["packages", "a"]));
+    assert!(!match_segments(&["packages", "*"], &["packages", "a", "b"]));
+}
@@ -1,6 +1,7 @@
 use nodejs_resolver::test_helper::{p, vec_to_set};
 use nodejs_resolver::{
-    AliasMap, Cache, EnforceExtension, Error, Options, ResolveResult, Resolver, Resource,
+    AliasKey, AliasMap, Cache, CheckLevel, Diagnostic, DynamicAlias, EnforceExtension, Error,
+    ModuleKind, Options, ResolveResult, Resolver, Resource, Restriction, Severity,
 };
 use path_absolutize::Absolutize;
 use std::path::{Path, PathBuf};
@@ -425,7 +426,8 @@ fn alias_test() {
                 vec![AliasMap::Target(String::from("./a?q2#f2"))],
             ),
             (String::from("ignore"), vec![AliasMap::Ignored]),
-        ],
+        ]
+        .into(),
         ..Default::default()
     });
 
@@ -746,7 +748,8 @@ fn alias_test() {
                 String::from("@A"),
                 vec![AliasMap::Target(String::from("./b"))],
             ),
-        ],
+        ]
+        .into(),
         ..Default::default()
     });
     should_equal(
@@ -765,7 +768,8 @@ fn alias_test() {
                 String::from("@A/index"),
                 vec![AliasMap::Target(String::from("./a"))],
             ),
-        ],
+        ]
+        .into(),
         ..Default::default()
     });
     should_equal(
@@ -800,7 +804,8 @@ fn rspack_issue_4779() {
         alias: vec![(
             String::from("@app"),
             vec![AliasMap::Target(base.to_string_lossy().to_string())],
-        )],
+        )]
+        .into(),
         symlinks: true,
         ..Default::default()
     });
@@ -852,7 +857,8 @@ fn fallback_test() {
                 String::from("./ff"),
                 vec![AliasMap::Target(String::from("./ccc"))],
             ),
-        ],
+        ]
+        .into(),
         ..Default::default()
     });
     // maybe better is `should_overflow(&resolver, &alias_cases_path, "./ee");`
@@ -1432,7 +1438,7 @@ fn browser_filed_test() {
     );
 
     let resolver = Resolver::new(Options {
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         ..Default::default()
     });
 
@@ -1565,11 +1571,12 @@ fn browser_filed_test() {
 
     // browser with alias
     let resolver = Resolver::new(Options {
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         alias: vec![(
             String::from("./lib/toString.js"),
             vec![AliasMap::Target(String::from("module-d"))],
-        )],
+        )]
+        .into(),
         ..Default::default()
     });
 
@@ -1655,8 +1662,93 @@ fn browser_filed_test() {
     should_ignored(&resolver, &browser_after_main_path, ".");
     should_ignored(&resolver, &p(vec![]), "./browser-after-main");
     should_ignored(&resolver, &browser_after_main_path, ".");
+}
+
+#[test]
+fn alias_fields_test() {
+    let fixture = p(vec!["alias-fields"]);
+    let cache = Arc::new(Cache::default());
+    let resolver = Resolver::new(Options {
+        alias_fields: vec![
+            vec![String::from("react-native")],
+            vec![String::from("browser")],
+        ],
+        external_cache: Some(cache.clone()),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./lib/platform",
+        p(vec!["alias-fields", "lib", "native.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture.join("lib"),
+        "module-a",
+        p(vec!["alias-fields", "lib", "native-module-a.js"]),
+    );
+
+    // the cache of alias fields is shared with the resolver above.
+    let resolver = Resolver::new(Options {
+        alias_fields: vec![
+            vec![String::from("browser")],
+            vec![String::from("react-native")],
+        ],
+        external_cache: Some(cache.clone()),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./lib/platform",
+        p(vec!["alias-fields", "lib", "browser.js"]),
+    );
+    should_ignored(&resolver, &fixture.join("lib"), "module-a");
+    // the self-pointing entry in "browser" falls through to "react-native".
+    should_equal(
+        &resolver,
+        &fixture,
+        "./lib/worker",
+        p(vec!["alias-fields", "lib", "native.js"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        alias_fields: vec![vec![String::from("custom"), String::from("field")]],
+        external_cache: Some(cache),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./lib/platform",
+        p(vec!["alias-fields", "lib", "custom.js"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        alias_fields: vec![vec![String::from("worker")]],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        ".",
+        p(vec!["alias-fields", "lib", "worker.js"]),
+    );
 
-    // TODO: alias_fields
+    let resolver = Resolver::new(Options::default());
+    should_equal(
+        &resolver,
+        &fixture,
+        "./lib/platform",
+        p(vec!["alias-fields", "lib", "platform.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        ".",
+        p(vec!["alias-fields", "lib", "index.js"]),
+    );
 }
 
 #[test]
@@ -1758,8 +1850,9 @@ fn fully_specified_test() {
                 String::from("alias2"),
                 vec![AliasMap::Target(p(vec!["full", "a"]).display().to_string())],
             ),
-        ],
-        browser_field: true,
+        ]
+        .into(),
+        alias_fields: vec![vec![String::from("browser")]],
         fully_specified: true,
         ..Default::default()
     });
@@ -1872,8 +1965,9 @@ fn fully_specified_test() {
                 String::from("alias2"),
                 vec![AliasMap::Target(p(vec!["full", "a"]).display().to_string())],
             ),
-        ],
-        browser_field: true,
+        ]
+        .into(),
+        alias_fields: vec![vec![String::from("browser")]],
         ..Default::default()
     });
     should_failed(&resolver, &full_cases_path.join(".."), ".");
@@ -2029,13 +2123,91 @@ fn incorrect_package_test() {
         ".",
         incorrect_package_path.join("pack2").join("package.json"),
     );
+    should_unexpected_json_error(
+        &resolver,
+        &incorrect_package_path,
+        "./nested/broken",
+        incorrect_package_path
+            .join("nested")
+            .join("broken")
+            .join("package.json"),
+    );
+
+    let resolver = Resolver::new(Options {
+        ignore_invalid_description_files: true,
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &incorrect_package_path.join("pack1"),
+        "./a",
+        p(vec!["incorrect-package", "pack1", "a.js"]),
+    );
+    match resolver.resolve(&incorrect_package_path, "./nested/broken") {
+        Ok(ResolveResult::Resource(resource)) => {
+            assert_eq!(
+                resource.path,
+                p(vec!["incorrect-package", "nested", "broken", "index.js"])
+            );
+            let description = resource.description.unwrap();
+            assert_eq!(description.data().name(), Some("nested"));
+        }
+        _ => unreachable!(),
+    }
+    // the warning is reported on every resolving though the result is memoized.
+    for _ in 0..2 {
+        let (result, diagnostics) =
+            resolver.resolve_with_diagnostics(&incorrect_package_path.join("pack1"), "./a");
+        assert!(result.is_ok());
+        let location = incorrect_package_path.join("pack1").join("package.json");
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::warning(
+                format!(
+                    "Ignored invalid description file '{}', EOF while parsing a value at line 3 column 0",
+                    location.display()
+                ),
+                location,
+            )]
+        );
+    }
+
+    // the strict and lenient resolvers never read the memoized result of each other.
+    let cache = Arc::new(Cache::default());
+    let lenient = Resolver::new(Options {
+        ignore_invalid_description_files: true,
+        external_cache: Some(cache.clone()),
+        ..Default::default()
+    });
+    let strict = Resolver::new(Options {
+        external_cache: Some(cache),
+        ..Default::default()
+    });
+    should_equal(
+        &lenient,
+        &incorrect_package_path.join("pack1"),
+        "./a",
+        p(vec!["incorrect-package", "pack1", "a.js"]),
+    );
+    should_unexpected_json_error(
+        &strict,
+        &incorrect_package_path.join("pack1"),
+        "./a",
+        incorrect_package_path.join("pack1").join("package.json"),
+    );
+    should_equal(
+        &lenient,
+        &incorrect_package_path.join("pack1"),
+        "./a",
+        p(vec!["incorrect-package", "pack1", "a.js"]),
+    );
 }
 
 #[test]
 fn scoped_packages_test() {
     let scoped_path = p(vec!["scoped"]);
     let resolver = Resolver::new(Options {
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         ..Default::default()
     });
     should_equal(
@@ -2256,7 +2428,7 @@ fn exports_field_test() {
 
     let resolver = Resolver::new(Options {
         extensions: vec![String::from(".js")],
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         condition_names: vec_to_set(vec!["webpack"]),
         ..Default::default()
     });
@@ -2287,7 +2459,7 @@ fn exports_field_test() {
     );
     let resolver = Resolver::new(Options {
         extensions: vec![String::from(".js")],
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         condition_names: vec_to_set(vec!["node"]),
         ..Default::default()
     });
@@ -2560,7 +2732,7 @@ fn exports_filed_test_4() {
 
     let resolver = Resolver::new(Options {
         extensions: vec![String::from(".js")],
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         exports_field: vec![vec![String::from("exportsField"), String::from("exports")]],
         ..Default::default()
     });
@@ -2578,7 +2750,7 @@ fn exports_filed_test_4() {
 
     let resolver = Resolver::new(Options {
         extensions: vec![String::from(".js")],
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         exports_field: vec![
             vec![String::from("exportsField"), String::from("exports")],
             vec![String::from("exports")],
@@ -2599,7 +2771,7 @@ fn exports_filed_test_4() {
 
     let resolver = Resolver::new(Options {
         extensions: vec![String::from(".js")],
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         exports_field: vec![
             vec![String::from("exports")],
             vec![String::from("exportsField"), String::from("exports")],
@@ -2620,7 +2792,7 @@ fn exports_filed_test_4() {
 
     let resolver = Resolver::new(Options {
         extensions: vec![String::from(".js")],
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         exports_field: vec![
             vec![String::from("ex")],
             vec![String::from("exportsField"), String::from("exports")],
@@ -2857,6 +3029,48 @@ fn imports_fields_test() {
         "#imports-field",
         p(vec!["imports-field", "b.js"]),
     );
+
+    let resolver = Resolver::new(Options {
+        extensions: vec![String::from(".js")],
+        imports_field: vec![
+            vec![String::from("other"), String::from("imports")],
+            vec![String::from("imports")],
+        ],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &import_cases_path,
+        "#b",
+        p(vec!["imports-field", "a.js"]),
+    );
+    should_unexpected_value_error(
+        &resolver,
+        &import_cases_path,
+        "#imports-field",
+        "Package path #imports-field can't imported in".to_string(),
+    );
+
+    let resolver = Resolver::new(Options {
+        extensions: vec![String::from(".js")],
+        imports_field: vec![
+            vec![String::from("publishConfig")],
+            vec![String::from("imports")],
+        ],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &import_cases_path,
+        "#imports-field",
+        p(vec!["imports-field", "b.js"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        imports_field: vec![],
+        ..Default::default()
+    });
+    should_failed(&resolver, &import_cases_path, "#imports-field");
 }
 
 #[test]
@@ -3024,7 +3238,7 @@ fn main_fields_test() {
     );
 
     let resolver = Resolver::new(Options {
-        main_fields: vec![String::from("module")],
+        main_fields: vec![vec![String::from("module")]],
         ..Default::default()
     });
 
@@ -3048,7 +3262,7 @@ fn main_fields_test() {
     );
 
     let resolver = Resolver::new(Options {
-        main_fields: vec![String::from("main"), String::from("module")],
+        main_fields: vec![vec![String::from("main")], vec![String::from("module")]],
 
         ..Default::default()
     });
@@ -3061,7 +3275,7 @@ fn main_fields_test() {
     );
 
     let resolver = Resolver::new(Options {
-        main_fields: vec![String::from("module"), String::from("main")],
+        main_fields: vec![vec![String::from("module")], vec![String::from("main")]],
         ..Default::default()
     });
 
@@ -3073,6 +3287,52 @@ fn main_fields_test() {
     );
 }
 
+#[test]
+fn nested_main_fields_test() {
+    let fixture = p(vec!["main-field-nested"]);
+    let resolver = Resolver::new(Options {
+        main_fields: vec![
+            vec![String::from("publishConfig"), String::from("main")],
+            vec![String::from("esnext"), String::from("main")],
+            vec![String::from("browser")],
+            vec![String::from("main")],
+        ],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./publish",
+        p(vec!["main-field-nested", "publish", "dist", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./esnext",
+        p(vec!["main-field-nested", "esnext", "esnext.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./browser",
+        p(vec!["main-field-nested", "browser", "browser.js"]),
+    );
+
+    let resolver = Resolver::new(Options::default());
+    should_equal(
+        &resolver,
+        &fixture,
+        "./publish",
+        p(vec!["main-field-nested", "publish", "src", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./browser",
+        p(vec!["main-field-nested", "browser", "main.js"]),
+    );
+}
+
 #[test]
 fn tsconfig_paths_test() {
     let tsconfig_path = p(vec!["tsconfig-paths"]);
@@ -3514,7 +3774,7 @@ fn shared_cache_test2() {
     let case_path = p(vec!["browser-module"]);
     let cache = Arc::new(Cache::default());
     let resolver1 = Resolver::new(Options {
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         external_cache: Some(cache.clone()),
         ..Default::default()
     });
@@ -3533,7 +3793,7 @@ fn shared_cache_test2() {
 
     let resolver3 = Resolver::new(Options {
         external_cache: Some(cache),
-        main_fields: vec!["module".to_string()],
+        main_fields: vec![vec!["module".to_string()]],
         ..Default::default()
     });
     should_equal(
@@ -3556,7 +3816,7 @@ fn empty_test() {
 fn browser_it_self() {
     let case_path = p(vec!["browser-to-self"]);
     let resolver = Resolver::new(Options {
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         condition_names: vec_to_set(vec!["browser"]),
         ..Default::default()
     });
@@ -3597,11 +3857,11 @@ fn browser_it_self() {
     );
     should_overflow(&resolver, &case_path, "c.js");
     let resolver = Resolver::new(Options {
-        browser_field: true,
+        alias_fields: vec![vec![String::from("browser")]],
         main_fields: vec![
-            "browser".to_string(),
-            "module".to_string(),
-            "main".to_string(),
+            vec!["browser".to_string()],
+            vec!["module".to_string()],
+            vec!["main".to_string()],
         ],
         ..Default::default()
     });
@@ -3618,11 +3878,11 @@ fn browser_it_self() {
         p(vec!["browser-to-self", "node_modules", "c.js", "c.js"]),
     );
     let resolver = Resolver::new(Options {
-        browser_field: false,
+        alias_fields: vec![],
         main_fields: vec![
-            "browser".to_string(),
-            "module".to_string(),
-            "main".to_string(),
+            vec!["browser".to_string()],
+            vec!["module".to_string()],
+            vec!["main".to_string()],
         ],
         ..Default::default()
     });
@@ -3639,7 +3899,7 @@ fn browser_it_self() {
         p(vec!["browser-to-self", "node_modules", "c.js", "c.js"]),
     );
     let resolver = Resolver::new(Options {
-        browser_field: false,
+        alias_fields: vec![],
         ..Default::default()
     });
     should_equal(
@@ -3861,3 +4121,2131 @@ fn extension_alias2() {
         p(vec!["extension-alias", "dir2", "index.js"]),
     );
 }
+
+#[test]
+fn types_test() {
+    let fixture = p(vec!["types"]);
+    let resolver = Resolver::new(Options {
+        types: true,
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture.join("src"),
+        "./foo",
+        p(vec!["types", "src", "foo.d.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture.join("src"),
+        "./foo.js",
+        p(vec!["types", "src", "foo.d.ts"]),
+    );
+    should_failed(&resolver, &fixture.join("src"), "./only-js");
+    should_failed(&resolver, &fixture.join("src"), "./only-js.js");
+    should_equal(
+        &resolver,
+        &fixture,
+        "types-field",
+        p(vec![
+            "types",
+            "node_modules",
+            "types-field",
+            "lib",
+            "index.d.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "typings-field",
+        p(vec![
+            "types",
+            "node_modules",
+            "typings-field",
+            "typings.d.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "types-condition",
+        p(vec![
+            "types",
+            "node_modules",
+            "types-condition",
+            "dist",
+            "index.d.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "types-versions",
+        p(vec![
+            "types",
+            "node_modules",
+            "types-versions",
+            "ts4",
+            "index.d.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "types-versions/sub",
+        p(vec![
+            "types",
+            "node_modules",
+            "types-versions",
+            "ts4",
+            "sub.d.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "untyped",
+        p(vec![
+            "types",
+            "node_modules",
+            "@types",
+            "untyped",
+            "index.d.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "untyped/sub",
+        p(vec![
+            "types",
+            "node_modules",
+            "@types",
+            "untyped",
+            "sub.d.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "@scope/untyped",
+        p(vec![
+            "types",
+            "node_modules",
+            "@types",
+            "scope__untyped",
+            "index.d.ts",
+        ]),
+    );
+
+    let resolver = Resolver::new(Options {
+        types: true,
+        typescript_version: String::from("3.9"),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "types-versions",
+        p(vec![
+            "types",
+            "node_modules",
+            "types-versions",
+            "index.d.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "types-versions/sub",
+        p(vec!["types", "node_modules", "types-versions", "sub.d.ts"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        types: true,
+        typescript_version: String::from("99.1"),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "types-versions/sub",
+        p(vec![
+            "types",
+            "node_modules",
+            "types-versions",
+            "ts99",
+            "sub.d.ts",
+        ]),
+    );
+
+    let resolver = Resolver::new(Default::default());
+    should_equal(
+        &resolver,
+        &fixture,
+        "types-field",
+        p(vec![
+            "types",
+            "node_modules",
+            "types-field",
+            "lib",
+            "index.js",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "untyped",
+        p(vec!["types", "node_modules", "untyped", "index.js"]),
+    );
+}
+
+#[test]
+fn typescript_module_resolution_test() {
+    let fixture = p(vec!["ts-module-resolution"]);
+    let src = fixture.join("src");
+    let src_file = |name: &str| p(vec!["ts-module-resolution", "src", name]);
+    let pkg_file = |path: Vec<&str>| {
+        let mut paths = vec!["ts-module-resolution", "node_modules"];
+        paths.extend(path);
+        p(paths)
+    };
+
+    let resolver = Resolver::new(Options::node10());
+    should_equal(&resolver, &src, "./a", src_file("a.ts"));
+    should_equal(&resolver, &src, "./a.js", src_file("a.ts"));
+    should_equal(&resolver, &src, "./b.js", src_file("b.tsx"));
+    should_equal(&resolver, &src, "./c.js", src_file("c.d.ts"));
+    should_equal(
+        &resolver,
+        &src,
+        "./dir",
+        p(vec!["ts-module-resolution", "src", "dir", "index.ts"]),
+    );
+    should_failed(&resolver, &src, "./js-only");
+    should_equal(
+        &resolver,
+        &src,
+        "pkg",
+        pkg_file(vec!["pkg", "lib", "index.d.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "legacy",
+        pkg_file(vec!["legacy", "index.d.ts"]),
+    );
+
+    let resolver = Resolver::new(Options::node16(ModuleKind::CommonJs));
+    should_equal(&resolver, &src, "./a", src_file("a.ts"));
+    should_equal(
+        &resolver,
+        &src,
+        "./dir",
+        p(vec!["ts-module-resolution", "src", "dir", "index.ts"]),
+    );
+    should_equal(&resolver, &src, "./common.cjs", src_file("common.cts"));
+    should_equal(
+        &resolver,
+        &src,
+        "pkg",
+        pkg_file(vec!["pkg", "cjs", "index.d.cts"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "pkg/sub",
+        pkg_file(vec!["pkg", "cjs", "sub.d.cts"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "legacy",
+        pkg_file(vec!["legacy", "index.d.ts"]),
+    );
+
+    let resolver = Resolver::new(Options::nodenext(ModuleKind::EsModule));
+    should_failed(&resolver, &src, "./a");
+    should_failed(&resolver, &src, "./dir");
+    should_equal(&resolver, &src, "./a.js", src_file("a.ts"));
+    should_equal(&resolver, &src, "./b.js", src_file("b.tsx"));
+    should_equal(&resolver, &src, "./esm.mjs", src_file("esm.mts"));
+    should_equal(
+        &resolver,
+        &src,
+        "pkg",
+        pkg_file(vec!["pkg", "esm", "index.d.mts"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "pkg/sub",
+        pkg_file(vec!["pkg", "esm", "sub.d.mts"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "legacy",
+        pkg_file(vec!["legacy", "index.d.ts"]),
+    );
+
+    let resolver = Resolver::new(Options::bundler());
+    should_equal(&resolver, &src, "./a", src_file("a.ts"));
+    should_equal(&resolver, &src, "./a.js", src_file("a.ts"));
+    should_equal(
+        &resolver,
+        &src,
+        "./dir",
+        p(vec!["ts-module-resolution", "src", "dir", "index.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "pkg",
+        pkg_file(vec!["pkg", "esm", "index.d.mts"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "pkg/sub",
+        pkg_file(vec!["pkg", "esm", "sub.d.mts"]),
+    );
+}
+
+#[test]
+fn typescript_baselines_test() {
+    // Each directory mirrors the test with the same name in the TypeScript
+    // repository, the expected paths come from its `.trace.json` baseline.
+    let fixture = |case: &str| p(vec!["ts-baselines", case]);
+    let file = |case: &str, path: Vec<&str>| {
+        let mut paths = vec!["ts-baselines", case];
+        paths.extend(path);
+        p(paths)
+    };
+
+    // compiler/moduleResolutionWithExtensions.ts
+    let case = "moduleResolutionWithExtensions";
+    let src = fixture(case).join("src");
+    let resolver = Resolver::new(Options::node10());
+    should_equal(&resolver, &src, "./a", file(case, vec!["src", "a.ts"]));
+    should_equal(&resolver, &src, "./a.js", file(case, vec!["src", "a.ts"]));
+    should_equal(
+        &resolver,
+        &src,
+        "./jquery",
+        file(case, vec!["src", "jquery.d.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "./jquery.js",
+        file(case, vec!["src", "jquery.d.ts"]),
+    );
+
+    // conformance/moduleResolution/bundler/bundlerRelative1.ts
+    let case = "bundlerRelative1";
+    let root = fixture(case);
+    let resolver = Resolver::new(Options::bundler());
+    for request in ["./dir", "./dir/index", "./dir/index.js"] {
+        should_equal(
+            &resolver,
+            &root,
+            request,
+            file(case, vec!["dir", "index.ts"]),
+        );
+    }
+    should_equal(
+        &resolver,
+        &root,
+        "./redirect",
+        file(case, vec!["foo", "index.ts"]),
+    );
+    should_failed(&resolver, &root, "./redirect/index");
+
+    // conformance/node/nodeModules1.ts
+    let case = "nodeModules1";
+    let root = fixture(case);
+    let resolver = Resolver::new(Options::node16(ModuleKind::EsModule));
+    should_equal(&resolver, &root, "./index.js", file(case, vec!["index.ts"]));
+    should_equal(
+        &resolver,
+        &root,
+        "./index.mjs",
+        file(case, vec!["index.mts"]),
+    );
+    should_equal(
+        &resolver,
+        &root,
+        "./index.cjs",
+        file(case, vec!["index.cts"]),
+    );
+    should_failed(&resolver, &root, "./index");
+    should_failed(&resolver, &root, "./subfolder");
+    should_equal(
+        &resolver,
+        &root,
+        "./subfolder/index.js",
+        file(case, vec!["subfolder", "index.ts"]),
+    );
+    let resolver = Resolver::new(Options::node16(ModuleKind::CommonJs));
+    should_equal(&resolver, &root, "./index", file(case, vec!["index.ts"]));
+    should_equal(
+        &resolver,
+        &root,
+        "./subfolder",
+        file(case, vec!["subfolder", "index.ts"]),
+    );
+
+    // conformance/node/nodeModulesPackageExports.ts
+    let case = "nodeModulesPackageExports";
+    let root = fixture(case);
+    let inner = |name: &str| file(case, vec!["node_modules", "inner", name]);
+    for kind in [ModuleKind::EsModule, ModuleKind::CommonJs] {
+        let resolver = Resolver::new(Options::nodenext(kind));
+        should_equal(&resolver, &root, "inner", inner("index.d.ts"));
+        should_equal(&resolver, &root, "inner/cjs", inner("index.d.cts"));
+        should_equal(&resolver, &root, "inner/mjs", inner("index.d.mts"));
+    }
+    // `exports` is ignored by node10.
+    let resolver = Resolver::new(Options::node10());
+    should_equal(&resolver, &root, "inner", inner("index.d.ts"));
+    should_failed(&resolver, &root, "inner/cjs");
+
+    // conformance/node/nodeModulesConditionalPackageExports.ts
+    let case = "nodeModulesConditionalPackageExports";
+    let root = fixture(case);
+    let inner = |name: &str| file(case, vec!["node_modules", "inner", name]);
+    let resolver = Resolver::new(Options::node16(ModuleKind::EsModule));
+    should_equal(&resolver, &root, "inner", inner("index.d.mts"));
+    let resolver = Resolver::new(Options::node16(ModuleKind::CommonJs));
+    should_equal(&resolver, &root, "inner", inner("index.d.cts"));
+    let resolver = Resolver::new(Options::bundler());
+    should_equal(&resolver, &root, "inner", inner("index.d.mts"));
+    let resolver = Resolver::new(Options::node10());
+    should_equal(&resolver, &root, "inner", inner("index.d.ts"));
+}
+
+#[test]
+fn extension_alias_fully_specified_test() {
+    let case = p(vec![
+        "ts-baselines",
+        "moduleResolutionWithExtensions",
+        "src",
+    ]);
+    // the aliased extensions are only tried for the fully specified
+    // requests in TypeScript resolution.
+    let resolver = Resolver::new(Options {
+        extension_alias: vec![(String::from(".js"), vec![String::from(".ts")])],
+        fully_specified: true,
+        ..Default::default()
+    });
+    should_failed(&resolver, &case, "./a.js");
+    let resolver = Resolver::new(Options {
+        extension_alias: vec![(String::from(".js"), vec![String::from(".ts")])],
+        fully_specified: true,
+        types: true,
+        ..Default::default()
+    });
+    should_equal(&resolver, &case, "./a.js", case.join("a.ts"));
+}
+
+#[test]
+fn tsconfig_compiler_options_test() {
+    let fixture = p(vec!["tsconfig-compiler-options"]);
+    let resolver = Resolver::new(Options {
+        extensions: vec![".ts".to_string(), ".json".to_string()],
+        tsconfig: Some(fixture.join("tsconfig.json")),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./src/button",
+        p(vec!["tsconfig-compiler-options", "src", "button.ios.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./src/view",
+        p(vec!["tsconfig-compiler-options", "src", "view.native.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./src/text",
+        p(vec!["tsconfig-compiler-options", "src", "text.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "pkg",
+        p(vec![
+            "tsconfig-compiler-options",
+            "node_modules",
+            "pkg",
+            "custom.js",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "#internal",
+        p(vec![
+            "tsconfig-compiler-options",
+            "src",
+            "internal-custom.ts",
+        ]),
+    );
+    should_failed(&resolver, &fixture, "./src/text.ts");
+    should_failed(&resolver, &fixture, "./src/data.json");
+    should_failed(&resolver, &fixture, "./src/data");
+    // `moduleSuffixes` is applied to the request with extension too.
+    should_equal(
+        &resolver,
+        &fixture,
+        "./src/legacy.js",
+        p(vec!["tsconfig-compiler-options", "src", "legacy.ios.js"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        extensions: vec![".ts".to_string(), ".json".to_string()],
+        tsconfig: Some(fixture.join("tsconfig.allow.json")),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./src/button",
+        p(vec!["tsconfig-compiler-options", "src", "button.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "pkg",
+        p(vec![
+            "tsconfig-compiler-options",
+            "node_modules",
+            "pkg",
+            "index.js",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./src/text.ts",
+        p(vec!["tsconfig-compiler-options", "src", "text.ts"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./src/data",
+        p(vec!["tsconfig-compiler-options", "src", "data.json"]),
+    );
+
+    // `resolveJsonModule` only disables `.json` when it is explicitly `false`.
+    for tsconfig in ["tsconfig.unset.json", "tsconfig.empty.json"] {
+        let resolver = Resolver::new(Options {
+            extensions: vec![".ts".to_string(), ".json".to_string()],
+            tsconfig: Some(fixture.join(tsconfig)),
+            ..Default::default()
+        });
+        should_equal(
+            &resolver,
+            &fixture,
+            "./src/data",
+            p(vec!["tsconfig-compiler-options", "src", "data.json"]),
+        );
+        should_equal(
+            &resolver,
+            &fixture,
+            "./src/data.json",
+            p(vec!["tsconfig-compiler-options", "src", "data.json"]),
+        );
+        should_equal(
+            &resolver,
+            &fixture,
+            "./src/legacy.js",
+            p(vec!["tsconfig-compiler-options", "src", "legacy.js"]),
+        );
+    }
+}
+
+#[test]
+fn redirect_to_source_test() {
+    let fixture = p(vec!["source-redirect", "packages"]);
+    let app = fixture.join("app").join("src");
+    let resolver = Resolver::new(Options {
+        redirect_to_source: true,
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &app,
+        "lib",
+        p(vec![
+            "source-redirect",
+            "packages",
+            "lib",
+            "src",
+            "index.ts",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &app,
+        "lib/utils",
+        p(vec![
+            "source-redirect",
+            "packages",
+            "lib",
+            "src",
+            "utils.tsx",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &app,
+        "lib/types",
+        p(vec![
+            "source-redirect",
+            "packages",
+            "lib",
+            "src",
+            "index.ts",
+        ]),
+    );
+    // not reached through symlinks
+    should_equal(
+        &resolver,
+        &fixture.join("lib"),
+        "./dist/index.js",
+        p(vec![
+            "source-redirect",
+            "packages",
+            "lib",
+            "dist",
+            "index.js",
+        ]),
+    );
+
+    // the symlinks above `node_modules` are not counted.
+    let linked_root = p(vec!["source-redirect", "linked-root"]);
+    should_equal(
+        &resolver,
+        &linked_root.join("lib"),
+        "./dist/index.js",
+        p(vec![
+            "source-redirect",
+            "packages",
+            "lib",
+            "dist",
+            "index.js",
+        ]),
+    );
+    should_equal(
+        &resolver,
+        &linked_root.join("app").join("src"),
+        "lib",
+        p(vec![
+            "source-redirect",
+            "packages",
+            "lib",
+            "src",
+            "index.ts",
+        ]),
+    );
+    // the output directories from `extends` are relative to the extended
+    // tsconfig, except the ones start with `${configDir}`.
+    let ext_lib_src = p(vec![
+        "source-redirect",
+        "packages",
+        "ext-lib",
+        "src",
+        "index.ts",
+    ]);
+    should_equal(&resolver, &app, "ext-lib", ext_lib_src.clone());
+    should_equal(&resolver, &app, "ext-lib/types", ext_lib_src);
+
+    let resolver = Resolver::new(Options::default());
+    should_equal(
+        &resolver,
+        &app,
+        "lib",
+        p(vec![
+            "source-redirect",
+            "packages",
+            "lib",
+            "dist",
+            "index.js",
+        ]),
+    );
+}
+
+#[test]
+fn workspace_test() {
+    let fixture = p(vec!["workspace"]);
+    let web = fixture.join("apps").join("web").join("src");
+    let resolver = Resolver::new(Options {
+        workspace: Some(fixture.clone()),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &web,
+        "@ws/foo",
+        p(vec!["workspace", "packages", "foo", "lib", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &web,
+        "@ws/foo/utils",
+        p(vec!["workspace", "packages", "foo", "lib", "utils.js"]),
+    );
+    // the workspace package takes precedence over `node_modules`.
+    should_equal(
+        &resolver,
+        &web,
+        "bar",
+        p(vec!["workspace", "packages", "bar-dir", "main.js"]),
+    );
+    should_equal(
+        &resolver,
+        &web,
+        "deep",
+        p(vec!["workspace", "tools", "nested", "deep", "index.js"]),
+    );
+    should_failed(&resolver, &web, "excluded");
+    // the importers outside the workspace can't see its packages.
+    should_failed(&resolver, &p(vec!["workspace-npm", "app"]), "@ws/foo");
+
+    // a relative root is resolved against the cwd.
+    let resolver = Resolver::new(Options {
+        workspace: Some(PathBuf::from("tests/fixtures/workspace/packages/..")),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &web,
+        "@ws/foo",
+        p(vec!["workspace", "packages", "foo", "lib", "index.js"]),
+    );
+
+    let resolver = Resolver::new(Options::default());
+    should_equal(
+        &resolver,
+        &web,
+        "bar",
+        p(vec![
+            "workspace",
+            "apps",
+            "web",
+            "node_modules",
+            "bar",
+            "stale.js",
+        ]),
+    );
+    should_failed(&resolver, &web, "@ws/foo");
+
+    let fixture = p(vec!["workspace-npm"]);
+    let resolver = Resolver::new(Options {
+        workspace: Some(fixture.clone()),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture.join("app"),
+        "a",
+        p(vec!["workspace-npm", "packages", "a", "index.js"]),
+    );
+}
+
+#[test]
+fn module_manifest_test() {
+    let fixture = p(vec!["module-manifest"]);
+    let app = fixture.join("app").join("src");
+    let resolver = Resolver::new(Options {
+        module_manifest: Some(fixture.join("manifest.json")),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &app,
+        "foo",
+        p(vec!["module-manifest", "pkgs", "foo", "lib", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &app,
+        "foo/feature",
+        p(vec!["module-manifest", "pkgs", "foo", "lib", "feature.js"]),
+    );
+    should_unexpected_value_error(
+        &resolver,
+        &app,
+        "foo/lib/feature.js",
+        format!(
+            "Package path ./lib/feature.js is not exported in {}",
+            p(vec!["module-manifest", "pkgs", "foo", "package.json"]).display()
+        ),
+    );
+    should_equal(
+        &resolver,
+        &app,
+        "bar",
+        p(vec!["module-manifest", "pkgs", "bar", "main.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture.join("app").join("legacy").join("src"),
+        "foo",
+        p(vec!["module-manifest", "pkgs", "foo-v1", "legacy.js"]),
+    );
+    // `node_modules` is not looked up.
+    should_failed(&resolver, &app, "baz");
+    should_equal(
+        &resolver,
+        &app,
+        "./index",
+        p(vec!["module-manifest", "app", "src", "index.js"]),
+    );
+
+    // a relative manifest path is resolved against the cwd.
+    let resolver = Resolver::new(Options {
+        module_manifest: Some(PathBuf::from(
+            "tests/fixtures/module-manifest/manifest.json",
+        )),
+        symlinks: true,
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &app,
+        "foo",
+        p(vec!["module-manifest", "pkgs", "foo", "lib", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture.join("app").join("legacy").join("src"),
+        "foo",
+        p(vec!["module-manifest", "pkgs", "foo-v1", "legacy.js"]),
+    );
+
+    let resolver = Resolver::new(Options::default());
+    should_equal(
+        &resolver,
+        &app,
+        "baz",
+        p(vec![
+            "module-manifest",
+            "app",
+            "node_modules",
+            "baz",
+            "index.js",
+        ]),
+    );
+
+    let resolver = Resolver::new(Options {
+        module_manifest: Some(fixture.join("malformed-manifest.json")),
+        ..Default::default()
+    });
+    should_unexpected_json_error(
+        &resolver,
+        &app,
+        "foo",
+        fixture.join("malformed-manifest.json"),
+    );
+}
+
+#[test]
+fn global_modules_test() {
+    let fixture = p(vec!["global-modules"]);
+    let project = fixture.join("project").join("src");
+    let resolver = Resolver::new(Options {
+        global_modules: true,
+        global_dirs: Some(vec![fixture.join("global")]),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &project,
+        "foo",
+        p(vec!["global-modules", "global", "foo", "index.js"]),
+    );
+    // the ancestors are looked up before the global folders.
+    should_equal(
+        &resolver,
+        &project,
+        "bar",
+        p(vec![
+            "global-modules",
+            "project",
+            "node_modules",
+            "bar",
+            "index.js",
+        ]),
+    );
+    should_failed(&resolver, &project, "baz");
+
+    let paths = resolver.lookup_paths(&project);
+    assert_eq!(paths[0], project.join("node_modules"));
+    assert_eq!(paths[1], fixture.join("project").join("node_modules"));
+    assert_eq!(paths.last(), resolver.lookup_paths(&fixture).last());
+    let global_index = paths
+        .iter()
+        .position(|path| path == &fixture.join("global"))
+        .unwrap();
+    assert_eq!(paths[global_index - 1], PathBuf::from("/node_modules"));
+
+    let resolver = Resolver::new(Options::default());
+    should_failed(&resolver, &project, "foo");
+    assert_eq!(
+        resolver.lookup_paths(&project).last(),
+        Some(&PathBuf::from("/node_modules"))
+    );
+}
+
+#[test]
+fn module_lookup_boundary_test() {
+    let fixture = p(vec!["lookup-boundary"]);
+    let project = fixture.join("project");
+    let src = project.join("src");
+    let resolver = Resolver::new(Options {
+        module_lookup_boundary: Some(project.clone()),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "inside",
+        p(vec![
+            "lookup-boundary",
+            "project",
+            "node_modules",
+            "inside",
+            "index.js",
+        ]),
+    );
+    should_failed(&resolver, &src, "outside");
+    assert_eq!(
+        resolver.lookup_paths(&src),
+        vec![src.join("node_modules"), project.join("node_modules")]
+    );
+    // the request outside of boundary is not affected.
+    should_equal(
+        &resolver,
+        &fixture,
+        "outside",
+        p(vec![
+            "lookup-boundary",
+            "node_modules",
+            "outside",
+            "index.js",
+        ]),
+    );
+
+    let resolver = Resolver::new(Options {
+        module_lookup_boundary: Some(src.join("..")),
+        ..Default::default()
+    });
+    should_failed(&resolver, &src, "outside");
+
+    let resolver = Resolver::new(Options::default());
+    should_equal(
+        &resolver,
+        &src,
+        "outside",
+        p(vec![
+            "lookup-boundary",
+            "node_modules",
+            "outside",
+            "index.js",
+        ]),
+    );
+}
+
+#[test]
+fn roots_test() {
+    let fixture = p(vec!["roots"]);
+    let src = fixture.join("src");
+    let resolver = Resolver::new(Options {
+        roots: vec![fixture.join("public"), fixture.join("static")],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "/assets/logo.png",
+        p(vec!["roots", "public", "assets", "logo.png"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "/assets/extra.css",
+        p(vec!["roots", "public", "assets", "extra.css"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        "/assets/only-static.css",
+        p(vec!["roots", "static", "assets", "only-static.css"]),
+    );
+    should_failed(&resolver, &src, "/assets/missing.css");
+    // fallback to absolute path
+    should_equal(
+        &resolver,
+        &src,
+        fixture.join("src").join("index").to_str().unwrap(),
+        p(vec!["roots", "src", "index.js"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        roots: vec![fixture.join("static")],
+        prefer_absolute: true,
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "/assets/extra.css",
+        p(vec!["roots", "static", "assets", "extra.css"]),
+    );
+    should_equal(
+        &resolver,
+        &src,
+        fixture.join("src").join("index").to_str().unwrap(),
+        p(vec!["roots", "src", "index.js"]),
+    );
+
+    let resolver = Resolver::new(Options::default());
+    should_failed(&resolver, &src, "/assets/logo.png");
+}
+
+#[test]
+fn restrictions_test() {
+    let fixture = p(vec!["roots"]);
+    let src = fixture.join("src");
+    let alias = vec![(
+        String::from("lib"),
+        vec![
+            AliasMap::Target(fixture.join("restricted").join("lib").display().to_string()),
+            AliasMap::Target(fixture.join("vendor").join("lib").display().to_string()),
+        ],
+    )];
+    let resolver = Resolver::new(Options {
+        alias: alias.clone().into(),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "lib",
+        p(vec!["roots", "restricted", "lib", "index.js"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        alias: alias.clone().into(),
+        restrictions: vec![Restriction::Path(fixture.join("vendor"))],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "lib",
+        p(vec!["roots", "vendor", "lib", "index.js"]),
+    );
+    should_failed(&resolver, &src, "./index");
+
+    let resolver = Resolver::new(Options {
+        alias: alias.into(),
+        restrictions: vec![Restriction::Regex(
+            regex::Regex::new(r"[\\/]vendor[\\/]").unwrap(),
+        )],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "lib",
+        p(vec!["roots", "vendor", "lib", "index.js"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        restrictions: vec![Restriction::Regex(regex::Regex::new(r"\.css$").unwrap())],
+        ..Default::default()
+    });
+    should_failed(&resolver, &src, "./index");
+
+    // the rejected request falls back to `fallback`.
+    let resolver = Resolver::new(Options {
+        alias: vec![(
+            String::from("lib"),
+            vec![AliasMap::Target(
+                fixture.join("restricted").join("lib").display().to_string(),
+            )],
+        )]
+        .into(),
+        fallback: vec![(
+            String::from("lib"),
+            vec![AliasMap::Target(
+                fixture.join("vendor").join("lib").display().to_string(),
+            )],
+        )]
+        .into(),
+        restrictions: vec![Restriction::Path(fixture.join("vendor"))],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "lib",
+        p(vec!["roots", "vendor", "lib", "index.js"]),
+    );
+
+    // the real path is checked.
+    let symlink = p(vec!["symlink"]);
+    let resolver = Resolver::new(Options {
+        restrictions: vec![Restriction::Path(symlink.join("lib"))],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &symlink.join("linked"),
+        "./index.js",
+        p(vec!["symlink", "lib", "index.js"]),
+    );
+    let resolver = Resolver::new(Options {
+        restrictions: vec![Restriction::Path(symlink.join("linked"))],
+        ..Default::default()
+    });
+    should_failed(&resolver, &symlink.join("linked"), "./index.js");
+}
+
+#[test]
+fn alias_pattern_test() {
+    let fixture = p(vec!["alias-pattern"]);
+    let resolver = Resolver::new(Options {
+        alias: vec![
+            (
+                AliasKey::from("@/*"),
+                vec![AliasMap::Target(String::from("./src/*"))],
+            ),
+            (
+                AliasKey::from("*.svg"),
+                vec![AliasMap::Target(String::from("./icons/*.js"))],
+            ),
+            (
+                AliasKey::from(regex::Regex::new(r"^~(.*)$").unwrap()),
+                vec![AliasMap::Target(String::from("./node_modules/$1"))],
+            ),
+            (
+                AliasKey::from(regex::Regex::new(r"^#(?P<name>\w+)$").unwrap()),
+                vec![AliasMap::Target(String::from("./src/${name}"))],
+            ),
+        ]
+        .into(),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "@/components/button",
+        p(vec!["alias-pattern", "src", "components", "button.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "@/utils",
+        p(vec!["alias-pattern", "src", "utils", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "logo.svg",
+        p(vec!["alias-pattern", "icons", "logo.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "~lodash",
+        p(vec!["alias-pattern", "node_modules", "lodash", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "#special",
+        p(vec!["alias-pattern", "src", "special", "index.js"]),
+    );
+    should_failed(&resolver, &fixture, "@/missing");
+
+    // the plain keys and the pattern keys are tried in order.
+    let resolver = Resolver::new(Options {
+        alias: vec![
+            (
+                String::from("@/utils$"),
+                vec![AliasMap::Target(String::from("./src/special"))],
+            ),
+            (
+                String::from("@/*"),
+                vec![AliasMap::Target(String::from("./src/*"))],
+            ),
+        ]
+        .into(),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "@/utils",
+        p(vec!["alias-pattern", "src", "special", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "@/utils/index",
+        p(vec!["alias-pattern", "src", "utils", "index.js"]),
+    );
+    let resolver = Resolver::new(Options {
+        alias: vec![
+            (
+                String::from("@/*"),
+                vec![AliasMap::Target(String::from("./src/*"))],
+            ),
+            (
+                String::from("@/utils$"),
+                vec![AliasMap::Target(String::from("./src/special"))],
+            ),
+        ]
+        .into(),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "@/utils",
+        p(vec!["alias-pattern", "src", "utils", "index.js"]),
+    );
+
+    // the key with the longer literal part wins, and the regex keys are the last.
+    let resolver = Resolver::new(Options {
+        alias: vec![
+            (
+                AliasKey::from(regex::Regex::new(r"^@/(.*)$").unwrap()),
+                vec![AliasMap::Target(String::from("./icons/logo"))],
+            ),
+            (
+                AliasKey::from("@/*"),
+                vec![AliasMap::Target(String::from("./src/*"))],
+            ),
+            (
+                AliasKey::from("@/utils/*"),
+                vec![AliasMap::Target(String::from("./src/special/*"))],
+            ),
+        ]
+        .into(),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "@/utils/index",
+        p(vec!["alias-pattern", "src", "special", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "@/components/button",
+        p(vec!["alias-pattern", "src", "components", "button.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "@/missing",
+        p(vec!["alias-pattern", "icons", "logo.js"]),
+    );
+
+    // a key is not applied to the request rewritten by itself.
+    let resolver = Resolver::new(Options {
+        alias: vec![(
+            AliasKey::from(regex::Regex::new(r"^(.*)$").unwrap()),
+            vec![AliasMap::Target(String::from("./src/$1"))],
+        )]
+        .into(),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "utils",
+        p(vec!["alias-pattern", "src", "utils", "index.js"]),
+    );
+    should_failed(&resolver, &fixture, "missing");
+
+    let resolver = Resolver::new(Options {
+        fallback: vec![(
+            String::from("missing/*"),
+            vec![
+                AliasMap::Target(String::from("./missing/*")),
+                AliasMap::Target(String::from("./src/*")),
+            ],
+        )]
+        .into(),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "missing/utils",
+        p(vec!["alias-pattern", "src", "utils", "index.js"]),
+    );
+}
+
+#[test]
+fn dynamic_alias_test() {
+    let fixture = p(vec!["alias-dynamic"]);
+    let mocks = fixture.join("mocks");
+    let resolver = Resolver::new(Options {
+        alias: vec![
+            (
+                String::from("utils"),
+                vec![
+                    AliasMap::Dynamic(DynamicAlias::new(move |context| {
+                        context.importer.ends_with("test").then(|| {
+                            vec![AliasMap::Target(
+                                mocks.join("utils.js").display().to_string(),
+                            )]
+                        })
+                    })),
+                    AliasMap::Target(String::from("./src/utils")),
+                ],
+            ),
+            (
+                String::from("feature"),
+                vec![AliasMap::Dynamic(DynamicAlias::new(|context| {
+                    let name = context.description?.data().name()?;
+                    let feature = if name == "dynamic-app" { "new" } else { "old" };
+                    Some(vec![AliasMap::Target(format!(
+                        "{}/src/feature/{feature}",
+                        context.description?.dir().as_ref().display()
+                    ))])
+                }))],
+            ),
+            (
+                String::from("ignored"),
+                vec![AliasMap::Dynamic(DynamicAlias::new(|context| {
+                    assert_eq!(context.request, "ignored");
+                    Some(vec![AliasMap::Ignored])
+                }))],
+            ),
+        ]
+        .into(),
+        fallback: vec![(
+            String::from("*"),
+            vec![AliasMap::Dynamic(DynamicAlias::new(|context| {
+                Some(vec![AliasMap::Target(format!(
+                    "./src/{}",
+                    context.request.trim_start_matches("missing-")
+                ))])
+            }))],
+        )]
+        .into(),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "utils",
+        p(vec!["alias-dynamic", "src", "utils.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture.join("test"),
+        "utils",
+        p(vec!["alias-dynamic", "mocks", "utils.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture.join("test"),
+        "feature",
+        p(vec!["alias-dynamic", "src", "feature", "new.js"]),
+    );
+    should_ignored(&resolver, &fixture, "ignored");
+    should_equal(
+        &resolver,
+        &fixture,
+        "missing-utils",
+        p(vec!["alias-dynamic", "src", "utils.js"]),
+    );
+}
+
+#[test]
+fn plugin_test() {
+    use nodejs_resolver::{Context, HookPoint, Info, ResolverPlugin, State};
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct TestPlugin {
+        module_lookups: Mutex<Vec<String>>,
+    }
+
+    impl ResolverPlugin for TestPlugin {
+        fn apply(
+            &self,
+            hook: HookPoint,
+            resolver: &Resolver,
+            info: Info,
+            context: &mut Context,
+        ) -> State {
+            let target = info.request().target();
+            match hook {
+                HookPoint::BeforeResolve => match target.strip_prefix("virtual:") {
+                    Some(name) => {
+                        let target = format!("./{name}");
+                        resolver.resolve_info(info.clone().with_target(&target), context)
+                    }
+                    None => State::Resolving(info),
+                },
+                HookPoint::BeforeModuleLookup => {
+                    self.module_lookups.lock().unwrap().push(target.to_string());
+                    State::Resolving(info)
+                }
+                HookPoint::Result if info.normalized_path().as_ref().ends_with("b.js") => {
+                    State::Failed(info)
+                }
+                HookPoint::OnFailed if target == "recoverable" => {
+                    let path = info.normalized_path().as_ref().join("fallback.js");
+                    State::Success(ResolveResult::Resource(Info::new(path, Default::default())))
+                }
+                _ => State::Resolving(info),
+            }
+        }
+    }
+
+    let fixture = p(vec!["plugin", "src"]);
+    let plugin = Arc::new(TestPlugin::default());
+    let resolver = Resolver::new(Options {
+        plugins: vec![plugin.clone()],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "virtual:a",
+        p(vec!["plugin", "src", "a.js"]),
+    );
+    should_equal(&resolver, &fixture, "./a", p(vec!["plugin", "src", "a.js"]));
+    should_failed(&resolver, &fixture, "./b");
+    should_equal(
+        &resolver,
+        &fixture,
+        "recoverable",
+        p(vec!["plugin", "src", "fallback.js"]),
+    );
+    should_failed(&resolver, &fixture, "unrecoverable");
+    let module_lookups = plugin.module_lookups.lock().unwrap();
+    assert_eq!(
+        module_lookups.first().map(String::as_str),
+        Some("recoverable")
+    );
+    assert!(module_lookups
+        .iter()
+        .any(|target| target == "unrecoverable"));
+    assert!(!module_lookups.iter().any(|target| target.starts_with("./")));
+}
+
+#[test]
+fn lifecycle_hooks_test() {
+    use nodejs_resolver::LifecycleHooks;
+    use std::sync::Mutex;
+
+    let fixture = p(vec!["plugin", "src"]);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let (start, resolved, failed, probe) = (
+        events.clone(),
+        events.clone(),
+        events.clone(),
+        events.clone(),
+    );
+    let resolver = Resolver::new(Options {
+        lifecycle_hooks: LifecycleHooks {
+            on_resolve_start: Some(Arc::new(move |_, request| {
+                start.lock().unwrap().push(format!("start {request}"))
+            })),
+            on_resolved: Some(Arc::new(move |resource| {
+                let name = resource.path.file_name().unwrap().to_string_lossy();
+                resolved.lock().unwrap().push(format!("resolved {name}"))
+            })),
+            on_failed: Some(Arc::new(move |err| {
+                failed.lock().unwrap().push(format!("failed {err:?}"))
+            })),
+            on_file_probe: Some(Arc::new(move |path, exists| {
+                let name = path.file_name().unwrap().to_string_lossy();
+                probe.lock().unwrap().push(format!("probe {name} {exists}"))
+            })),
+        },
+        ..Default::default()
+    });
+    should_equal(&resolver, &fixture, "./a", p(vec!["plugin", "src", "a.js"]));
+    let taken = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(taken.first().unwrap(), "start ./a");
+    let probe_a = taken.iter().position(|event| event == "probe a false");
+    let probe_a_js = taken.iter().position(|event| event == "probe a.js true");
+    assert!(probe_a.unwrap() < probe_a_js.unwrap());
+    assert_eq!(taken.last().unwrap(), "resolved a.js");
+
+    // the cached entries are not probed again.
+    should_equal(&resolver, &fixture, "./a", p(vec!["plugin", "src", "a.js"]));
+    let taken = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(taken, vec!["start ./a", "resolved a.js"]);
+
+    // the description files are probed as well.
+    should_equal(
+        &resolver,
+        &p(vec!["main-field"]),
+        "./src",
+        p(vec!["main-field", "src", "index.js"]),
+    );
+    let taken = std::mem::take(&mut *events.lock().unwrap());
+    assert!(taken.contains(&String::from("probe package.json true")));
+    assert!(taken.contains(&String::from("probe src true")));
+
+    should_failed(&resolver, &fixture, "./c");
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(events.first().unwrap(), "start ./c");
+    assert!(events.contains(&String::from("probe c.json false")));
+    assert_eq!(events.last().unwrap(), "failed ResolveFailedTag");
+}
+
+#[test]
+fn description_files_test() {
+    let fixture = p(vec!["description-files"]);
+    let cache = Arc::new(Cache::default());
+    let resolver = Resolver::new(Options {
+        description_files: vec![String::from("package.json"), String::from("component.json")],
+        external_cache: Some(cache.clone()),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./component",
+        p(vec!["description-files", "component", "main.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./both",
+        p(vec!["description-files", "both", "package.js"]),
+    );
+    let name = |resolver: &Resolver, request: &str| match resolver.resolve(&fixture, request) {
+        Ok(ResolveResult::Resource(resource)) => resource
+            .description
+            .and_then(|description| description.data().name().map(String::from)),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        name(&resolver, "./nested/lib"),
+        Some(String::from("nested-component"))
+    );
+    should_unexpected_value_error(
+        &resolver,
+        &fixture,
+        "exported/hidden.js",
+        format!(
+            "Package path exported/hidden.js is not exported in {}",
+            p(vec![
+                "description-files",
+                "node_modules",
+                "exported",
+                "component.json"
+            ])
+            .display()
+        ),
+    );
+
+    // the entries are shared, but the description data is memoized separately.
+    let resolver = Resolver::new(Options {
+        description_files: vec![String::from("component.json"), String::from("package.json")],
+        external_cache: Some(cache.clone()),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./both",
+        p(vec!["description-files", "both", "component.js"]),
+    );
+
+    let resolver = Resolver::new(Options {
+        external_cache: Some(cache),
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./component",
+        p(vec!["description-files", "component", "index.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./both",
+        p(vec!["description-files", "both", "package.js"]),
+    );
+    assert_ne!(
+        name(&resolver, "./nested/lib"),
+        Some(String::from("nested-component"))
+    );
+}
+
+#[test]
+fn description_formats_test() {
+    let fixture = p(vec!["description-formats"]);
+    let resolver = Resolver::new(Options {
+        description_files: vec![
+            String::from("package.json"),
+            String::from("package.json5"),
+            String::from("package.yaml"),
+            String::from("package.toml"),
+        ],
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &fixture,
+        "./yaml",
+        p(vec!["description-formats", "yaml", "main.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "./json5",
+        p(vec!["description-formats", "json5", "main.js"]),
+    );
+    should_equal(
+        &resolver,
+        &fixture,
+        "yaml-pkg",
+        p(vec![
+            "description-formats",
+            "node_modules",
+            "yaml-pkg",
+            "lib",
+            "index.js",
+        ]),
+    );
+    should_unexpected_value_error(
+        &resolver,
+        &fixture,
+        "yaml-pkg/lib/index.js",
+        format!(
+            "Package path yaml-pkg/lib/index.js is not exported in {}",
+            p(vec![
+                "description-formats",
+                "node_modules",
+                "yaml-pkg",
+                "package.yaml"
+            ])
+            .display()
+        ),
+    );
+    should_unexpected_value_error(
+        &resolver,
+        &fixture,
+        "./unknown",
+        format!(
+            "No parser for description file {}",
+            p(vec!["description-formats", "unknown", "package.toml"]).display()
+        ),
+    );
+
+    fn parse_toml(content: &str, _: &Path) -> Result<serde_json::Value, Error> {
+        let name = content
+            .trim()
+            .trim_start_matches("name = ")
+            .trim_matches('"');
+        Ok(serde_json::json!({ "name": name, "main": "./index.js" }))
+    }
+    // every format reports the malformed file with its location.
+    for (request, file, line) in [
+        ("./broken-json5", "package.json5", 3),
+        ("./broken-yaml", "package.yaml", 3),
+    ] {
+        match resolver.resolve(&fixture, request) {
+            Err(Error::UnexpectedJson((path, error))) => {
+                assert_eq!(*path, *p(vec!["description-formats", &request[2..], file]));
+                assert_eq!(error.line, line);
+                assert!(error.column > 0);
+            }
+            result => panic!("{result:?}"),
+        }
+    }
+
+    let cache = Arc::new(Cache::default());
+    let resolver = Resolver::new(Options {
+        description_files: vec![String::from("package.toml")],
+        external_cache: Some(cache.clone()),
+        ..Default::default()
+    });
+    should_unexpected_value_error(
+        &resolver,
+        &fixture,
+        "./unknown",
+        format!(
+            "No parser for description file {}",
+            p(vec!["description-formats", "unknown", "package.toml"]).display()
+        ),
+    );
+    // the description data is memoized for each set of parsers.
+    let mut description_parsers = Options::default().description_parsers;
+    description_parsers.push((String::from("toml"), parse_toml));
+    let resolver = Resolver::new(Options {
+        description_files: vec![String::from("package.toml")],
+        description_parsers,
+        external_cache: Some(cache),
+        ..Default::default()
+    });
+    match resolver.resolve(&fixture, "./unknown") {
+        Ok(ResolveResult::Resource(resource)) => {
+            assert_eq!(
+                resource.path,
+                p(vec!["description-formats", "unknown", "index.js"])
+            );
+            let description = resource.description.unwrap();
+            assert_eq!(description.data().name(), Some("unknown"));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn diagnostics_test() {
+    let fixture = p(vec!["diagnostics"]);
+    let resolver = Resolver::new(Options {
+        alias_fields: vec![vec![String::from("browser")]],
+        ..Default::default()
+    });
+    let diagnostics = |resolver: &Resolver, request: &str, expected: PathBuf| {
+        let (result, diagnostics) = resolver.resolve_with_diagnostics(&fixture, request);
+        match result {
+            Ok(ResolveResult::Resource(resource)) => assert_eq!(resource.path, expected),
+            _ => unreachable!(),
+        }
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity == Severity::Warning));
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| (diagnostic.message, diagnostic.location, diagnostic.key_path))
+            .collect::<Vec<_>>()
+    };
+    let keys = |keys: &[&str]| keys.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        diagnostics(
+            &resolver,
+            "folder-exports/lib/a.js",
+            p(vec!["diagnostics", "node_modules", "folder-exports", "lib", "a.js"])
+        ),
+        vec![(
+            String::from("Use of deprecated folder mapping for 'folder-exports/lib/a.js' in the \"exports\" field, use subpath patterns instead"),
+            p(vec!["diagnostics", "node_modules", "folder-exports", "package.json"]),
+            keys(&["exports", "./lib/"])
+        )]
+    );
+    assert_eq!(
+        diagnostics(
+            &resolver,
+            "./browser-self/lib/self.js",
+            p(vec!["diagnostics", "browser-self", "lib", "self.js"])
+        ),
+        vec![(
+            String::from("'./lib/self.js' points to itself in the \"browser\" field"),
+            p(vec!["diagnostics", "browser-self", "package.json"]),
+            keys(&["browser", "./lib/self.js"])
+        )]
+    );
+    assert_eq!(
+        diagnostics(
+            &resolver,
+            "./main-missing",
+            p(vec!["diagnostics", "main-missing", "index.js"])
+        ),
+        vec![(
+            String::from("'./missing.js' in the \"main\" field can't be resolved, fallback to the next field or the main files"),
+            p(vec!["diagnostics", "main-missing", "package.json"]),
+            keys(&["main"])
+        )]
+    );
+    assert!(diagnostics(
+        &resolver,
+        "./main-missing/index.js",
+        p(vec!["diagnostics", "main-missing", "index.js"])
+    )
+    .is_empty());
+
+    let resolver = Resolver::new(Options {
+        tsconfig: Some(fixture.join("tsconfig.json")),
+        ..Default::default()
+    });
+    assert_eq!(
+        diagnostics(
+            &resolver,
+            "./main-missing/index.js",
+            p(vec!["diagnostics", "main-missing", "index.js"])
+        ),
+        vec![(
+            String::from("Can't resolve './missing.json' in the \"extends\" field, it is skipped"),
+            p(vec!["diagnostics", "tsconfig.json"]),
+            keys(&["extends"])
+        )]
+    );
+}
+
+#[test]
+fn suggestions_test() {
+    let fixture = p(vec!["suggestions"]);
+    let resolver = Resolver::new(Options {
+        suggestions: true,
+        ..Default::default()
+    });
+    let suggestions = |request: &str| {
+        let (result, diagnostics) = resolver.resolve_with_diagnostics(&fixture, request);
+        assert!(result.is_err());
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Hint)
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        suggestions("./src/button"),
+        vec![
+            "Did you mean './src/button.tsx'?",
+            "Did you mean './src/buttons.js'?"
+        ]
+    );
+    assert_eq!(suggestions("raect"), vec!["Did you mean 'react'?"]);
+    assert_eq!(
+        suggestions("@scope/pgk"),
+        vec!["Did you mean '@scope/pkg'?"]
+    );
+    assert_eq!(
+        suggestions("react/lib/dmo"),
+        vec!["Did you mean 'react/lib/dom.js'?"]
+    );
+    assert_eq!(
+        suggestions("exported/featur"),
+        vec![
+            "Did you mean 'exported/feature'?",
+            "Did you mean 'exported'?",
+            "Did you mean 'exported/package.json'?"
+        ]
+    );
+    assert_eq!(suggestions("#util"), vec!["Did you mean '#utils'?"]);
+    // the pattern keys are not suggested.
+    for request in ["#internal", "exported/features"] {
+        assert!(!suggestions(request)
+            .iter()
+            .any(|suggestion| suggestion.contains('*')));
+    }
+    assert!(suggestions("not-similar-at-all").is_empty());
+
+    // the candidates are attached to the error of plain `resolve`.
+    match resolver.resolve(&fixture, "raect") {
+        Err(Error::Suggested((error, candidates))) => {
+            assert!(matches!(*error, Error::ResolveFailedTag));
+            assert_eq!(candidates, vec![String::from("react")]);
+        }
+        result => panic!("{result:?}"),
+    }
+    assert!(matches!(
+        resolver.resolve(&fixture, "not-similar-at-all"),
+        Err(Error::ResolveFailedTag)
+    ));
+
+    let resolver = Resolver::new(Options::default());
+    let (result, diagnostics) = resolver.resolve_with_diagnostics(&fixture, "raect");
+    assert!(matches!(result, Err(Error::ResolveFailedTag)));
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn case_mismatch_test() {
+    let fixture = p(vec!["case-mismatch"]);
+    let resolver = Resolver::new(Options {
+        case_mismatch: CheckLevel::Report,
+        ..Default::default()
+    });
+    let (result, diagnostics) = resolver.resolve_with_diagnostics(&fixture, "./src/button");
+    match result {
+        Ok(ResolveResult::Resource(resource)) => {
+            assert_eq!(resource.path, p(vec!["case-mismatch", "src", "button.js"]))
+        }
+        _ => unreachable!(),
+    }
+    assert!(diagnostics.is_empty());
+
+    // only reproducible on the case-insensitive file system.
+    if !fixture.join("src").join("BUTTON.js").exists() {
+        return;
+    }
+    let (result, diagnostics) = resolver.resolve_with_diagnostics(&fixture, "./src/Button");
+    assert!(result.is_ok());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].location,
+        p(vec!["case-mismatch", "src", "Button.js"])
+    );
+    let resolver = Resolver::new(Options {
+        case_mismatch: CheckLevel::Fail,
+        ..Default::default()
+    });
+    match resolver.resolve(&fixture, "./src/Button") {
+        Err(Error::CaseMismatch((location, message))) => {
+            assert_eq!(&*location, p(vec!["case-mismatch", "src", "Button.js"]));
+            assert!(message.ends_with(&format!(
+                "mismatches the case of the actual path '{}'",
+                p(vec!["case-mismatch", "src", "button.js"]).display()
+            )));
+        }
+        result => panic!("{result:?}"),
+    }
+}
+
+#[test]
+fn ambiguity_test() {
+    let fixture = p(vec!["ambiguity"]);
+    let resolver = Resolver::new(Options {
+        extensions: vec![String::from(".ts"), String::from(".js")],
+        main_files: vec![String::from("index"), String::from("main")],
+        detect_ambiguity: true,
+        ..Default::default()
+    });
+    let shadowed = |path: &Path, request: &str, expected: PathBuf| {
+        let (result, diagnostics) = resolver.resolve_with_diagnostics(path, request);
+        match result {
+            Ok(ResolveResult::Resource(resource)) => assert_eq!(resource.path, expected),
+            _ => unreachable!(),
+        }
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.location)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        shadowed(&fixture, "./src/foo", p(vec!["ambiguity", "src", "foo.ts"])),
+        vec![p(vec!["ambiguity", "src", "foo.js"])]
+    );
+    assert_eq!(
+        shadowed(&fixture, "./dir", p(vec!["ambiguity", "dir", "index.js"])),
+        vec![p(vec!["ambiguity", "dir", "main.js"])]
+    );
+    assert_eq!(
+        shadowed(
+            &fixture.join("src"),
+            "ambiguous-pkg",
+            p(vec![
+                "ambiguity",
+                "src",
+                "node_modules",
+                "ambiguous-pkg",
+                "index.js"
+            ])
+        ),
+        vec![p(vec![
+            "ambiguity",
+            "node_modules",
+            "ambiguous-pkg",
+            "index.js"
+        ])]
+    );
+    assert!(shadowed(
+        &fixture,
+        "./src/foo.js",
+        p(vec!["ambiguity", "src", "foo.js"])
+    )
+    .is_empty());
+    // the package resolved through the main field.
+    assert_eq!(
+        shadowed(
+            &fixture.join("src"),
+            "main-pkg",
+            p(vec![
+                "ambiguity",
+                "src",
+                "node_modules",
+                "main-pkg",
+                "lib",
+                "entry.js"
+            ])
+        ),
+        vec![p(vec![
+            "ambiguity",
+            "node_modules",
+            "main-pkg",
+            "dist",
+            "entry.js"
+        ])]
+    );
+
+    // the links to the same package are not shadowed.
+    let resolver = Resolver::new(Options {
+        symlinks: false,
+        detect_ambiguity: true,
+        ..Default::default()
+    });
+    let (result, diagnostics) =
+        resolver.resolve_with_diagnostics(&fixture.join("src"), "linked-pkg");
+    match result {
+        Ok(ResolveResult::Resource(resource)) => assert_eq!(
+            resource.path,
+            p(vec![
+                "ambiguity",
+                "src",
+                "node_modules",
+                "linked-pkg",
+                "index.js"
+            ])
+        ),
+        _ => unreachable!(),
+    }
+    assert!(diagnostics.is_empty());
+
+    let resolver = Resolver::new(Options {
+        extensions: vec![String::from(".ts"), String::from(".js")],
+        ..Default::default()
+    });
+    let (result, diagnostics) = resolver.resolve_with_diagnostics(&fixture, "./src/foo");
+    assert!(result.is_ok());
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn phantom_dependencies_test() {
+    let fixture = p(vec!["phantom"]);
+    let src = fixture.join("src");
+    let resolver = Resolver::new(Options {
+        phantom_dependencies: CheckLevel::Report,
+        ..Default::default()
+    });
+    let diagnostics = |request: &str, expected: PathBuf| {
+        let (result, diagnostics) = resolver.resolve_with_diagnostics(&src, request);
+        match result {
+            Ok(ResolveResult::Resource(resource)) => assert_eq!(resource.path, expected),
+            _ => unreachable!(),
+        }
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.location)
+            .collect::<Vec<_>>()
+    };
+    assert!(diagnostics(
+        "declared",
+        p(vec!["phantom", "node_modules", "declared", "index.js"])
+    )
+    .is_empty());
+    assert!(diagnostics(
+        "@scope/dev/index.js",
+        p(vec!["phantom", "node_modules", "@scope", "dev", "index.js"])
+    )
+    .is_empty());
+    assert!(diagnostics("./index", p(vec!["phantom", "src", "index.js"])).is_empty());
+    assert_eq!(
+        diagnostics(
+            "hoisted",
+            p(vec!["phantom", "node_modules", "hoisted", "index.js"])
+        ),
+        vec![p(vec!["phantom", "package.json"])]
+    );
+
+    let resolver = Resolver::new(Options {
+        phantom_dependencies: CheckLevel::Fail,
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "declared",
+        p(vec!["phantom", "node_modules", "declared", "index.js"]),
+    );
+    match resolver.resolve(&src, "hoisted") {
+        Err(Error::PhantomDependency((location, message))) => {
+            assert_eq!(&*location, p(vec!["phantom", "package.json"]));
+            assert_eq!(
+                message,
+                format!(
+                    "'hoisted' is resolved to '{}', but it is not declared in the dependencies of {}",
+                    p(vec!["phantom", "node_modules", "hoisted", "index.js"]).display(),
+                    p(vec!["phantom", "package.json"]).display()
+                )
+            );
+        }
+        result => panic!("{result:?}"),
+    }
+}
+
+#[test]
+fn version_mismatch_test() {
+    let fixture = p(vec!["phantom"]);
+    let src = fixture.join("src");
+    let resolver = Resolver::new(Options {
+        version_mismatch: CheckLevel::Report,
+        ..Default::default()
+    });
+    let diagnostics = |request: &str| {
+        let (result, diagnostics) = resolver.resolve_with_diagnostics(&src, request);
+        assert!(result.is_ok());
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| (diagnostic.message, diagnostic.location))
+            .collect::<Vec<_>>()
+    };
+    assert!(diagnostics("fresh").is_empty());
+    assert!(diagnostics("local").is_empty());
+    // without version
+    assert!(diagnostics("declared").is_empty());
+    // phantom dependencies are not checked.
+    assert!(diagnostics("hoisted").is_empty());
+    assert_eq!(
+        diagnostics("stale/index.js"),
+        vec![(
+            format!(
+                "'stale@1.5.0' located at '{}' doesn't satisfy '^2.0.0' declared in the dependencies of {}",
+                p(vec!["phantom", "node_modules", "stale"]).display(),
+                p(vec!["phantom", "package.json"]).display()
+            ),
+            p(vec!["phantom", "node_modules", "stale", "package.json"])
+        )]
+    );
+    // the diagnostics are attached to the resource of plain `resolve`.
+    match resolver.resolve(&src, "stale/index.js") {
+        Ok(ResolveResult::Resource(resource)) => {
+            let (_, diagnostics) = resolver.resolve_with_diagnostics(&src, "stale/index.js");
+            assert_eq!(resource.diagnostics, diagnostics);
+            assert_eq!(resource.diagnostics.len(), 1);
+        }
+        _ => unreachable!(),
+    }
+
+    let resolver = Resolver::new(Options {
+        version_mismatch: CheckLevel::Fail,
+        ..Default::default()
+    });
+    should_equal(
+        &resolver,
+        &src,
+        "fresh",
+        p(vec!["phantom", "node_modules", "fresh", "index.js"]),
+    );
+    match resolver.resolve(&src, "stale") {
+        Err(Error::VersionMismatch((location, message))) => {
+            assert_eq!(
+                &*location,
+                p(vec!["phantom", "node_modules", "stale", "package.json"])
+            );
+            assert!(message.contains("'stale@1.5.0' located at"));
+        }
+        result => panic!("{result:?}"),
+    }
+}